@@ -47,6 +47,8 @@ pub fn derive_account(item: proc_macro::TokenStream) -> proc_macro::TokenStream
             const OWNER: Pubkey = crate::ID;
         }
 
+        impl ProgramOwned for #name #ty_generics #where_clause {}
+
         impl Discriminator for #name #ty_generics #where_clause {
             const DISCRIMINATOR: &'static [u8] = &[#(#discriminator),*];
         }
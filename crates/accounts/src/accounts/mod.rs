@@ -7,6 +7,7 @@ mod mutable;
 mod program;
 mod signer;
 mod system;
+mod sysvar;
 mod unchecked;
 
 #[cfg(feature = "borsh")]
@@ -19,5 +20,6 @@ pub use {
     program::*,
     signer::{Signer, SignerCheck, SignerNoCheck},
     system::*,
+    sysvar::*,
     unchecked::*,
 };
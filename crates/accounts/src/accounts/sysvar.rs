@@ -0,0 +1,74 @@
+use {
+    crate::{FromAccountInfo, ReadableAccount, SysvarData},
+    core::marker::PhantomData,
+    pinocchio::{
+        account_info::{AccountInfo, Ref},
+        hint::unlikely,
+        program_error::ProgramError,
+        pubkey::pubkey_eq,
+    },
+    typhoon_errors::Error,
+};
+
+///
+/// Checks:
+/// * `account_info.key == T::ID`
+pub struct Sysvar<'a, T: ?Sized> {
+    info: &'a AccountInfo,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T> FromAccountInfo<'a> for Sysvar<'a, T>
+where
+    T: SysvarData + ?Sized,
+{
+    #[inline]
+    fn try_from_info(info: &'a AccountInfo) -> Result<Self, Error> {
+        if unlikely(!pubkey_eq(info.key(), &T::ID)) {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+
+        Ok(Sysvar {
+            info,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<'a, T: ?Sized> From<Sysvar<'a, T>> for &'a AccountInfo {
+    #[inline(always)]
+    fn from(value: Sysvar<'a, T>) -> Self {
+        value.info
+    }
+}
+
+impl<T: ?Sized> AsRef<AccountInfo> for Sysvar<'_, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &AccountInfo {
+        self.info
+    }
+}
+
+impl<T> ReadableAccount for Sysvar<'_, T>
+where
+    T: SysvarData + ?Sized,
+{
+    type DataUnchecked = T;
+    type Data<'a>
+        = Ref<'a, T>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn data<'a>(&'a self) -> Result<Self::Data<'a>, Error> {
+        Ref::filter_map(self.info.try_borrow_data()?, |data| {
+            T::from_bytes(data).ok()
+        })
+        .map_err(|_| ProgramError::InvalidAccountData.into())
+    }
+
+    #[inline]
+    fn data_unchecked(&self) -> Result<&Self::DataUnchecked, Error> {
+        T::from_bytes(unsafe { self.info.borrow_data_unchecked() })
+    }
+}
@@ -1,6 +1,6 @@
 #![no_std]
 
-pub use {accounts::*, discriminator::*, programs::*};
+pub use {accounts::*, discriminator::*, programs::*, sysvars::*};
 use {
     bytemuck::{AnyBitPattern, NoUninit},
     pinocchio::{
@@ -14,6 +14,7 @@ use {
 mod accounts;
 mod discriminator;
 mod programs;
+mod sysvars;
 
 pub trait FromAccountInfo<'a>: Sized {
     fn try_from_info(info: &'a AccountInfo) -> Result<Self, Error>;
@@ -102,6 +103,9 @@ pub trait Owner {
     const OWNER: Pubkey;
 }
 
+/// Accounts owned by the program itself, implemented by `#[derive(AccountState)]`.
+pub trait ProgramOwned: Owner {}
+
 pub trait Owners {
     const OWNERS: &'static [Pubkey];
 }
@@ -0,0 +1,460 @@
+use {
+    crate::{Discriminator, ProgramId},
+    bytemuck::{Pod, Zeroable},
+    pinocchio::{
+        program_error::ProgramError,
+        pubkey::{pubkey_eq, Pubkey, PUBKEY_BYTES},
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            instructions::{
+                Instructions as InstructionsSysvar, IntrospectedAccountMeta, INSTRUCTIONS_ID,
+            },
+            rent::{Rent, RENT_ID},
+            slot_hashes::{SlotHashes as SlotHashesSysvar, SLOTHASHES_ID},
+        },
+    },
+    pinocchio_pubkey::pubkey,
+    typhoon_errors::{Error, ErrorCode},
+};
+
+pub trait SysvarId {
+    const ID: Pubkey;
+}
+
+/// Zero-copy view over the account data of a sysvar.
+pub trait SysvarData: SysvarId {
+    fn from_bytes(data: &[u8]) -> Result<&Self, Error>;
+}
+
+impl SysvarId for Rent {
+    const ID: Pubkey = RENT_ID;
+}
+
+impl SysvarData for Rent {
+    #[inline(always)]
+    fn from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        Rent::from_bytes(data).map_err(Into::into)
+    }
+}
+
+impl SysvarId for Clock {
+    const ID: Pubkey = CLOCK_ID;
+}
+
+impl SysvarData for Clock {
+    #[inline(always)]
+    fn from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        Clock::from_bytes(data).map_err(Into::into)
+    }
+}
+
+/// Data of the instructions sysvar, every instruction is checked to be within the data when it
+/// is created.
+#[repr(transparent)]
+pub struct Instructions([u8]);
+
+impl Instructions {
+    #[inline(always)]
+    pub fn sysvar(&self) -> InstructionsSysvar<&[u8]> {
+        // SAFETY: `from_bytes` checked that the offsets, the account metas, the program ids and
+        // the data of all the instructions are within the data.
+        unsafe { InstructionsSysvar::new_unchecked(&self.0) }
+    }
+
+    /// Number of instructions in the transaction.
+    #[inline(always)]
+    pub fn num_instructions(&self) -> u16 {
+        read_u16(&self.0, 0).unwrap_or_default()
+    }
+
+    /// Index of the instruction being executed.
+    #[inline(always)]
+    pub fn current_index(&self) -> u16 {
+        let len = self.0.len();
+        u16::from_le_bytes([self.0[len - 2], self.0[len - 1]])
+    }
+
+    #[inline]
+    pub fn load_instruction_at(&self, index: usize) -> Result<IntrospectedInstruction<'_>, Error> {
+        if index >= self.num_instructions() as usize {
+            return Err(ProgramError::InvalidInstructionData.into());
+        }
+
+        let offset_pos = U16_SIZE + index * U16_SIZE;
+        let offset = read_u16(&self.0, offset_pos)? as usize;
+
+        IntrospectedInstruction::parse(
+            self.0
+                .get(offset..)
+                .ok_or(ProgramError::InvalidAccountData)?,
+        )
+    }
+
+    #[inline]
+    pub fn get_instruction_relative(
+        &self,
+        index_relative_to_current: i64,
+    ) -> Result<IntrospectedInstruction<'_>, Error> {
+        let index = (self.current_index() as i64).saturating_add(index_relative_to_current);
+        if index < 0 {
+            return Err(ProgramError::InvalidInstructionData.into());
+        }
+
+        self.load_instruction_at(index as usize)
+    }
+
+    /// Iterates over all the instructions of the transaction.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Result<IntrospectedInstruction<'_>, Error>> {
+        (0..self.num_instructions() as usize).map(|index| self.load_instruction_at(index))
+    }
+
+    /// Returns the instruction at `index_relative_to_current` if it is the instruction `H`.
+    /// `handlers!` emits such marker types in its `discriminators` module with `#![markers]`.
+    #[inline]
+    pub fn require_instruction_relative<H>(
+        &self,
+        index_relative_to_current: i64,
+    ) -> Result<IntrospectedInstruction<'_>, Error>
+    where
+        H: ProgramId + Discriminator,
+    {
+        let instruction = self
+            .get_instruction_relative(index_relative_to_current)
+            .map_err(|_| ErrorCode::UnexpectedInstruction)?;
+
+        if !instruction.is_instruction::<H>() {
+            return Err(ErrorCode::UnexpectedInstruction.into());
+        }
+
+        Ok(instruction)
+    }
+
+    #[inline(always)]
+    pub fn require_next_instruction<H>(&self) -> Result<IntrospectedInstruction<'_>, Error>
+    where
+        H: ProgramId + Discriminator,
+    {
+        self.require_instruction_relative::<H>(1)
+    }
+
+    #[inline(always)]
+    pub fn require_previous_instruction<H>(&self) -> Result<IntrospectedInstruction<'_>, Error>
+    where
+        H: ProgramId + Discriminator,
+    {
+        self.require_instruction_relative::<H>(-1)
+    }
+}
+
+/// Zero-copy view over an instruction stored in the instructions sysvar.
+#[derive(Clone, Copy)]
+pub struct IntrospectedInstruction<'a> {
+    accounts: &'a [u8],
+    program_id: &'a Pubkey,
+    data: &'a [u8],
+}
+
+impl<'a> IntrospectedInstruction<'a> {
+    fn parse(raw: &'a [u8]) -> Result<Self, Error> {
+        let num_accounts = read_u16(raw, 0)? as usize;
+        let accounts_end = U16_SIZE + num_accounts * ACCOUNT_META_SIZE;
+        let program_id_end = accounts_end + PUBKEY_BYTES;
+        let data_len = read_u16(raw, program_id_end)? as usize;
+        let data_start = program_id_end + U16_SIZE;
+
+        let (Some(accounts), Some(program_id), Some(data)) = (
+            raw.get(U16_SIZE..accounts_end),
+            raw.get(accounts_end..program_id_end),
+            raw.get(data_start..data_start + data_len),
+        ) else {
+            return Err(ProgramError::InvalidAccountData.into());
+        };
+
+        Ok(IntrospectedInstruction {
+            accounts,
+            // SAFETY: The slice is exactly `PUBKEY_BYTES` long.
+            program_id: unsafe { &*(program_id.as_ptr() as *const Pubkey) },
+            data,
+        })
+    }
+
+    #[inline(always)]
+    pub fn program_id(&self) -> &'a Pubkey {
+        self.program_id
+    }
+
+    #[inline(always)]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    #[inline(always)]
+    pub fn num_accounts(&self) -> usize {
+        self.accounts.len() / ACCOUNT_META_SIZE
+    }
+
+    #[inline(always)]
+    pub fn account_meta_at(&self, index: usize) -> Option<&'a IntrospectedAccountMeta> {
+        let start = index * ACCOUNT_META_SIZE;
+        let meta = self.accounts.get(start..start + ACCOUNT_META_SIZE)?;
+
+        // SAFETY: `IntrospectedAccountMeta` has an alignment of 1 and the slice has its size.
+        Some(unsafe { &*(meta.as_ptr() as *const IntrospectedAccountMeta) })
+    }
+
+    #[inline]
+    pub fn account_metas(&self) -> impl Iterator<Item = &'a IntrospectedAccountMeta> + '_ {
+        (0..self.num_accounts()).filter_map(|index| self.account_meta_at(index))
+    }
+
+    /// Returns `true` if the instruction is the instruction `H` of the program `H::ID`.
+    #[inline(always)]
+    pub fn is_instruction<H>(&self) -> bool
+    where
+        H: ProgramId + Discriminator,
+    {
+        pubkey_eq(self.program_id, &H::ID) && self.data.starts_with(H::DISCRIMINATOR)
+    }
+}
+
+const U16_SIZE: usize = core::mem::size_of::<u16>();
+const ACCOUNT_META_SIZE: usize = core::mem::size_of::<IntrospectedAccountMeta>();
+
+#[inline(always)]
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, Error> {
+    data.get(offset..offset + U16_SIZE)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(ProgramError::InvalidAccountData.into())
+}
+
+impl SysvarId for Instructions {
+    const ID: Pubkey = INSTRUCTIONS_ID;
+}
+
+impl SysvarData for Instructions {
+    #[inline(always)]
+    fn from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        if data.len() < 2 * U16_SIZE {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        // SAFETY: `Instructions` is a transparent wrapper over `[u8]`.
+        let instructions = unsafe { &*(data as *const [u8] as *const Instructions) };
+        for index in 0..instructions.num_instructions() as usize {
+            instructions.load_instruction_at(index)?;
+        }
+
+        Ok(instructions)
+    }
+}
+
+/// Data of the slot hashes sysvar.
+#[repr(transparent)]
+pub struct SlotHashes([u8]);
+
+impl SlotHashes {
+    #[inline(always)]
+    pub fn sysvar(&self) -> SlotHashesSysvar<&[u8]> {
+        // SAFETY: The length is validated in `from_bytes`.
+        unsafe { SlotHashesSysvar::new_unchecked(&self.0) }
+    }
+}
+
+impl SysvarId for SlotHashes {
+    const ID: Pubkey = SLOTHASHES_ID;
+}
+
+impl SysvarData for SlotHashes {
+    #[inline(always)]
+    fn from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        SlotHashesSysvar::new(data)?;
+
+        // SAFETY: `SlotHashes` is a transparent wrapper over `[u8]`.
+        Ok(unsafe { &*(data as *const [u8] as *const SlotHashes) })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct StakeHistoryEntry {
+    pub epoch: u64,
+    pub effective: u64,
+    pub activating: u64,
+    pub deactivating: u64,
+}
+
+/// Data of the stake history sysvar, entries are sorted from the newest epoch.
+#[repr(transparent)]
+pub struct StakeHistory([u8]);
+
+impl StakeHistory {
+    const LEN_SIZE: usize = core::mem::size_of::<u64>();
+
+    #[inline(always)]
+    pub fn entries(&self) -> &[StakeHistoryEntry] {
+        let len = u64::from_le_bytes(self.0[..Self::LEN_SIZE].try_into().unwrap()) as usize;
+        let end = Self::LEN_SIZE + len * core::mem::size_of::<StakeHistoryEntry>();
+
+        bytemuck::try_cast_slice(&self.0[Self::LEN_SIZE..end]).unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn get(&self, epoch: u64) -> Option<&StakeHistoryEntry> {
+        let entries = self.entries();
+        entries
+            .binary_search_by(|entry| epoch.cmp(&entry.epoch))
+            .ok()
+            .map(|index| &entries[index])
+    }
+}
+
+impl SysvarId for StakeHistory {
+    const ID: Pubkey = pubkey!("SysvarStakeHistory1111111111111111111111111");
+}
+
+impl SysvarData for StakeHistory {
+    #[inline(always)]
+    fn from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        let len = data
+            .get(..Self::LEN_SIZE)
+            .and_then(|len| len.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let expected = (len as usize)
+            .checked_mul(core::mem::size_of::<StakeHistoryEntry>())
+            .and_then(|size| size.checked_add(Self::LEN_SIZE))
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if data.len() < expected {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        // SAFETY: `StakeHistory` is a transparent wrapper over `[u8]`.
+        Ok(unsafe { &*(data as *const [u8] as *const StakeHistory) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use {super::*, std::vec::Vec};
+
+    struct Borrow;
+
+    impl ProgramId for Borrow {
+        const ID: Pubkey = [7; 32];
+    }
+
+    impl Discriminator for Borrow {
+        const DISCRIMINATOR: &'static [u8] = &[0];
+    }
+
+    struct Repay;
+
+    impl ProgramId for Repay {
+        const ID: Pubkey = [7; 32];
+    }
+
+    impl Discriminator for Repay {
+        const DISCRIMINATOR: &'static [u8] = &[1];
+    }
+
+    type TestInstruction<'a> = (Pubkey, &'a [(u8, Pubkey)], &'a [u8]);
+
+    fn instructions_data(ixs: &[TestInstruction], current: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(ixs.len() as u16).to_le_bytes());
+        let mut offset = 2 + 2 * ixs.len();
+        let mut body = Vec::new();
+        for (program_id, metas, ix_data) in ixs {
+            data.extend_from_slice(&(offset as u16).to_le_bytes());
+            let start = body.len();
+            body.extend_from_slice(&(metas.len() as u16).to_le_bytes());
+            for (flags, key) in metas.iter() {
+                body.push(*flags);
+                body.extend_from_slice(key);
+            }
+            body.extend_from_slice(program_id);
+            body.extend_from_slice(&(ix_data.len() as u16).to_le_bytes());
+            body.extend_from_slice(ix_data);
+            offset += body.len() - start;
+        }
+        data.extend_from_slice(&body);
+        data.extend_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_instructions_introspection() {
+        let data = instructions_data(
+            &[
+                ([7; 32], &[(0b11, [1; 32]), (0, [2; 32])], &[0, 42]),
+                ([9; 32], &[], &[]),
+                ([7; 32], &[(0b10, [1; 32])], &[1]),
+            ],
+            0,
+        );
+        let Ok(instructions) = Instructions::from_bytes(&data) else {
+            panic!("invalid instructions sysvar");
+        };
+
+        assert_eq!(instructions.num_instructions(), 3);
+        assert_eq!(instructions.current_index(), 0);
+        assert_eq!(instructions.iter().filter(|ix| ix.is_ok()).count(), 3);
+
+        let Ok(current) = instructions.get_instruction_relative(0) else {
+            panic!("missing current instruction");
+        };
+        assert_eq!(current.program_id(), &[7; 32]);
+        assert_eq!(current.data(), &[0, 42]);
+        assert_eq!(current.num_accounts(), 2);
+        let metas: Vec<_> = current.account_metas().collect();
+        assert!(metas[0].is_signer() && metas[0].is_writable());
+        assert_eq!(metas[1].key, [2; 32]);
+        assert!(current.is_instruction::<Borrow>());
+
+        assert!(instructions.require_next_instruction::<Repay>().is_err());
+        assert!(instructions
+            .require_instruction_relative::<Repay>(2)
+            .is_ok());
+        assert!(instructions
+            .require_previous_instruction::<Borrow>()
+            .is_err());
+        assert!(instructions.load_instruction_at(3).is_err());
+        assert_eq!(
+            instructions
+                .sysvar()
+                .load_instruction_at(2)
+                .map(|ix| *ix.get_program_id()),
+            Ok([7; 32])
+        );
+
+        assert!(Instructions::from_bytes(&data[..data.len() - 3]).is_err());
+
+        let mut bad_offset = data;
+        bad_offset[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(Instructions::from_bytes(&bad_offset).is_err());
+        assert!(Instructions::from_bytes(&[1, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_stake_history() {
+        let mut data = [0u64; 1 + 2 * 4];
+        data[0] = 2;
+        data[1..5].copy_from_slice(&[11, 100, 10, 1]);
+        data[5..9].copy_from_slice(&[10, 90, 0, 0]);
+        let bytes: &[u8] = bytemuck::cast_slice(&data);
+
+        let Ok(history) = StakeHistory::from_bytes(bytes) else {
+            panic!("invalid stake history");
+        };
+        assert_eq!(history.entries().len(), 2);
+        assert_eq!(history.get(10).unwrap().effective, 90);
+        assert_eq!(history.get(11).unwrap().activating, 10);
+        assert!(history.get(12).is_none());
+
+        assert!(StakeHistory::from_bytes(&bytes[..40]).is_err());
+    }
+}
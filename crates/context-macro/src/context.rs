@@ -1,18 +1,43 @@
 use {
-    crate::{injector::LifetimeInjector, remover::AttributeRemover},
+    crate::{
+        injector::{FieldInjector, LifetimeInjector},
+        remover::AttributeRemover,
+    },
     syn::{parse::Parse, spanned::Spanned, visit_mut::VisitMut, Item, ItemStruct},
-    typhoon_syn::{constraints::CONSTRAINT_IDENT_STR, Arguments, InstructionAccount},
+    typhoon_syn::{
+        constraints::CONSTRAINT_IDENT_STR, event_cpi_fields, parse_context_fields, Arguments,
+        InstructionAccount, NestedContext, EVENT_CPI_IDENT_STR, FLATTEN_IDENT_STR,
+    },
 };
 
 pub struct ParsingContext {
     pub item_struct: ItemStruct,
     pub accounts: Vec<InstructionAccount>,
     pub args: Option<Arguments>,
+    pub nested: Vec<NestedContext>,
+    pub event_cpi: bool,
 }
 
 impl Parse for ParsingContext {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut item: Item = input.parse()?;
+
+        let event_cpi = match item {
+            Item::Struct(ref mut item_struct) => {
+                let event_cpi = item_struct
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident(EVENT_CPI_IDENT_STR));
+                if event_cpi {
+                    for field in event_cpi_fields() {
+                        FieldInjector::new(field).visit_item_struct_mut(item_struct);
+                    }
+                }
+                event_cpi
+            }
+            _ => false,
+        };
+
         LifetimeInjector.visit_item_mut(&mut item);
 
         match item {
@@ -24,19 +49,19 @@ impl Parse for ParsingContext {
                     .map(Arguments::try_from)
                     .transpose()?;
 
-                let accounts: Vec<InstructionAccount> = item_struct
-                    .fields
-                    .iter()
-                    .map(InstructionAccount::try_from)
-                    .collect::<syn::Result<_>>()?;
+                let (accounts, nested) = parse_context_fields(&item_struct.fields)?;
 
                 AttributeRemover::new("args").visit_item_struct_mut(&mut item_struct);
+                AttributeRemover::new(EVENT_CPI_IDENT_STR).visit_item_struct_mut(&mut item_struct);
                 AttributeRemover::new(CONSTRAINT_IDENT_STR).visit_item_struct_mut(&mut item_struct);
+                AttributeRemover::new(FLATTEN_IDENT_STR).visit_item_struct_mut(&mut item_struct);
 
                 Ok(ParsingContext {
                     item_struct,
                     accounts,
                     args,
+                    nested,
+                    event_cpi,
                 })
             }
             _ => Err(syn::Error::new(
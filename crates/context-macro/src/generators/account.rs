@@ -3,6 +3,9 @@ use {
     quote::{format_ident, quote},
     syn::{parse_quote, Expr, Ident},
     typhoon_syn::{
+        constraints::{
+            ConstraintAddress, ConstraintCustom, ConstraintMintExtension, ConstraintOwner,
+        },
         error,
         utils::{ContextExpr, SeedsExpr},
         InstructionAccount,
@@ -10,15 +13,10 @@ use {
 };
 
 pub enum AccountType {
-    TokenAccount {
-        is_ata: bool,
-        mint: Option<Ident>,
-        owner: Option<Expr>,
-    },
-    Mint {
-        decimals: Option<Expr>,
-        authority: Option<Expr>,
-        freeze_authority: Box<Option<Expr>>,
+    TokenAccount(Box<TokenAccountContext>),
+    Mint(Box<MintContext>),
+    Multisig {
+        signers: Option<Option<Expr>>,
     },
     Other {
         space: Option<Expr>,
@@ -26,6 +24,27 @@ pub enum AccountType {
     },
 }
 
+#[derive(Default)]
+pub struct TokenAccountContext {
+    pub is_ata: bool,
+    pub mint: Option<(Ident, Option<Expr>)>,
+    pub owner: Option<(Expr, Option<Expr>)>,
+    pub token_program: Option<Ident>,
+    pub delegate: Option<(Expr, Option<Expr>)>,
+    pub close_authority: Option<(Expr, Option<Expr>)>,
+    pub is_frozen: Option<(Expr, Option<Expr>)>,
+    pub native: Option<Option<Expr>>,
+}
+
+#[derive(Default)]
+pub struct MintContext {
+    pub decimals: Option<(Expr, Option<Expr>)>,
+    pub authority: Option<(Expr, Option<Expr>)>,
+    pub freeze_authority: Option<(Expr, Option<Expr>)>,
+    pub token_program: Option<Ident>,
+    pub extensions: Vec<ConstraintMintExtension>,
+}
+
 #[derive(Default)]
 pub struct InitContext {
     pub is_init_if_needed: bool,
@@ -38,6 +57,13 @@ pub struct PdaContext {
     pub bump: Option<ContextExpr>,
     pub is_seeded: bool,
     pub program_id: Option<Expr>,
+    pub error: Option<Expr>,
+}
+
+#[derive(Default)]
+pub struct ReallocContext {
+    pub size: Option<Expr>,
+    pub payer: Option<Ident>,
 }
 
 pub struct AccountGenerator<'a> {
@@ -46,6 +72,11 @@ pub struct AccountGenerator<'a> {
     pub init: Option<InitContext>,
     pub pda: Option<PdaContext>,
     pub init_state: bool,
+    pub close: Option<Ident>,
+    pub realloc: Option<ReallocContext>,
+    pub customs: Vec<ConstraintCustom>,
+    pub address: Option<ConstraintAddress>,
+    pub owner: Option<ConstraintOwner>,
 }
 
 impl<'a> AccountGenerator<'a> {
@@ -56,6 +87,11 @@ impl<'a> AccountGenerator<'a> {
             init: None,
             pda: Default::default(),
             init_state: false,
+            close: None,
+            realloc: None,
+            customs: Vec::new(),
+            address: None,
+            owner: None,
         }
     }
 }
@@ -63,17 +99,21 @@ impl<'a> AccountGenerator<'a> {
 impl AccountGenerator<'_> {
     pub fn needs_programs(&self) -> Vec<String> {
         let mut programs = Vec::with_capacity(3);
+        if self.realloc.is_some() {
+            programs.push("System".to_string());
+        }
+
         if self.init.is_some() {
             programs.push("System".to_string());
-            match self.account_ty {
-                AccountType::TokenAccount { is_ata, .. } => {
+            match &self.account_ty {
+                AccountType::TokenAccount(token) => {
                     programs.push("TokenProgram".to_string());
 
-                    if is_ata {
+                    if token.is_ata {
                         programs.push("AtaTokenProgram".to_string());
                     }
                 }
-                AccountType::Mint { .. } => programs.push("TokenProgram".to_string()),
+                AccountType::Mint(_) => programs.push("TokenProgram".to_string()),
                 _ => (),
             }
         }
@@ -81,8 +121,8 @@ impl AccountGenerator<'_> {
     }
 
     pub fn is_init_signer(&self) -> bool {
-        match self.account_ty {
-            AccountType::TokenAccount { is_ata: true, .. } => true,
+        match &self.account_ty {
+            AccountType::TokenAccount(token) if token.is_ata => true,
             _ => self.pda.is_some() || self.account.meta.is_signer,
         }
     }
@@ -217,40 +257,69 @@ impl AccountGenerator<'_> {
         ))?;
 
         let init_token = match &self.account_ty {
-            AccountType::TokenAccount {
-                is_ata,
-                mint,
-                owner,
-            } => {
-                let Some(owner) = owner else {
+            AccountType::TokenAccount(token) => {
+                let TokenAccountContext {
+                    is_ata,
+                    mint,
+                    owner,
+                    token_program,
+                    ..
+                } = token.as_ref();
+                let Some((owner, _)) = owner else {
                     error!(name, "An `owner` need to be specified for the `init` or `init_if_needed` constraint.");
                 };
-                let Some(mint) = mint else {
+                let Some((mint, _)) = mint else {
                     error!(name, "A `mint` need to be specified for the `init` or `init_if_needed` constraint.");
                 };
 
                 if *is_ata {
-                    quote!(SplCreateToken::create_associated_token_account(#name, &#payer, &#mint, &#owner, &system_program, &token_program)?)
+                    let token_program = token_program
+                        .clone()
+                        .unwrap_or_else(|| format_ident!("token_program"));
+                    quote!(SplCreateToken::create_associated_token_account(#name, &#payer, &#mint, &#owner, &system_program, &#token_program)?)
                 } else {
                     quote!(SplCreateToken::create_token_account(#name, &rent, &#payer, &#mint, &#owner, #signers)?)
                 }
             }
-            AccountType::Mint {
-                decimals,
-                authority,
-                freeze_authority,
-            } => {
+            AccountType::Mint(mint) => {
+                let MintContext {
+                    decimals,
+                    authority,
+                    freeze_authority,
+                    token_program,
+                    extensions,
+                } = mint.as_ref();
                 let default_decimals = parse_quote!(9);
-                let decimals = decimals.as_ref().unwrap_or(&default_decimals);
-                let Some(authority) = authority else {
+                let decimals = decimals
+                    .as_ref()
+                    .map(|(decimals, _)| decimals)
+                    .unwrap_or(&default_decimals);
+                let Some((authority, _)) = authority else {
                     error!(name, "An `authority` need to be specified for the `init` or `init_if_needed` constraint.");
                 };
-                let f_auth_token = if let Some(auth) = freeze_authority.as_ref() {
+                let f_auth_token = if let Some((auth, _)) = freeze_authority.as_ref() {
                     quote!(Some(#auth))
                 } else {
                     quote!(None)
                 };
-                quote!(SplCreateMint::create_mint(#name, &rent, &#payer, &#authority, #decimals, #f_auth_token, #signers)?)
+                match token_program.as_ref() {
+                    Some(token_program) => {
+                        let extensions = extensions.iter().map(mint_extension_token);
+                        quote!(SplCreateMint::create_mint_with_extensions(#name, &rent, &#payer, #token_program.key(), &#authority, #decimals, #f_auth_token, &[#(#extensions),*], #signers)?)
+                    }
+                    None if !extensions.is_empty() => {
+                        error!(
+                            name,
+                            "`mint::extensions` require the `mint::token_program` constraint."
+                        );
+                    }
+                    None => {
+                        quote!(SplCreateMint::create_mint(#name, &rent, &#payer, &#authority, #decimals, #f_auth_token, #signers)?)
+                    }
+                }
+            }
+            AccountType::Multisig { .. } => {
+                error!(name, "`init` cannot be used on the `Multisig` type.");
             }
             AccountType::Other { space, .. } => {
                 let account_ty = &self.account.inner_ty;
@@ -280,36 +349,160 @@ impl AccountGenerator<'_> {
         if let Some(ref pda_ctx) = self.pda {
             let pda = self.get_pda(pda_ctx, false, true)?;
             token.extend(pda);
+            let error = pda_ctx
+                .error
+                .as_ref()
+                .map(|error| quote!(Error::from(#error)))
+                .unwrap_or(quote!(Error::new(ProgramError::InvalidSeeds)));
             token.extend(quote! {
                 if #name.key() != &#pda_key {
-                    return Err(Error::new(ProgramError::InvalidSeeds).with_account(#name_str));
+                    return Err(#error.with_account(#name_str));
                 }
             });
         }
 
         match self.account_ty {
-            AccountType::TokenAccount {
-                ref mint,
-                ref owner,
-                ..
-            } => {
-                if let Some(mint) = mint {
+            AccountType::TokenAccount(ref token_account) => {
+                let TokenAccountContext {
+                    mint,
+                    owner,
+                    token_program,
+                    delegate,
+                    close_authority,
+                    is_frozen,
+                    native,
+                    ..
+                } = token_account.as_ref();
+                if let Some(token_program) = token_program {
+                    token.extend(quote! {
+                        if !#name.is_owned_by(#token_program.key()) {
+                            return Err(Error::from(ErrorCode::OwnerConstraint).with_account(#name_str));
+                        }
+                    });
+                }
+
+                let basic_error: Expr = parse_quote!(ErrorCode::TokenConstraintViolated);
+                if let Some((mint, error)) = mint {
+                    let error = error.as_ref().unwrap_or(&basic_error);
                     token.extend(quote! {
                         if #var_name.mint() != #mint.key() {
-                            return Err(ErrorCode::TokenConstraintViolated.into());
+                            return Err(Error::from(#error).with_account(#name_str));
                         }
                     });
                 }
 
-                if let Some(owner) = owner {
+                if let Some((owner, error)) = owner {
+                    let error = error.as_ref().unwrap_or(&basic_error);
                     token.extend(quote! {
                         if #var_name.owner() != #owner.key() {
-                            return Err(ErrorCode::TokenConstraintViolated.into());
+                            return Err(Error::from(#error).with_account(#name_str));
+                        }
+                    });
+                }
+
+                if let Some((delegate, error)) = delegate.as_ref() {
+                    let basic_error: Expr = parse_quote!(ErrorCode::TokenDelegateConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        if #var_name.delegate() != Some(#delegate.key()) {
+                            return Err(Error::from(#error).with_account(#name_str));
+                        }
+                    });
+                }
+
+                if let Some((close_authority, error)) = close_authority.as_ref() {
+                    let basic_error: Expr = parse_quote!(ErrorCode::TokenCloseAuthorityConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        if #var_name.close_authority() != Some(#close_authority.key()) {
+                            return Err(Error::from(#error).with_account(#name_str));
+                        }
+                    });
+                }
+
+                if let Some((is_frozen, error)) = is_frozen.as_ref() {
+                    let basic_error: Expr = parse_quote!(ErrorCode::TokenFrozenConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        if #var_name.is_frozen() != #is_frozen {
+                            return Err(Error::from(#error).with_account(#name_str));
+                        }
+                    });
+                }
+
+                if let Some(error) = native {
+                    let basic_error: Expr = parse_quote!(ErrorCode::TokenNativeConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        if !#var_name.is_native_mint() {
+                            return Err(Error::from(#error).with_account(#name_str));
+                        }
+                    });
+                }
+            }
+            AccountType::Mint(ref mint) => {
+                let MintContext {
+                    decimals,
+                    authority,
+                    freeze_authority,
+                    token_program,
+                    ..
+                } = mint.as_ref();
+                if let Some(token_program) = token_program.as_ref() {
+                    token.extend(quote! {
+                        if !#name.is_owned_by(#token_program.key()) {
+                            return Err(Error::from(ErrorCode::OwnerConstraint).with_account(#name_str));
+                        }
+                    });
+                }
+
+                if let Some((decimals, error)) = decimals {
+                    let basic_error: Expr = parse_quote!(ErrorCode::MintDecimalsConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        if #var_name.decimals() != #decimals {
+                            return Err(Error::from(#error).with_account(#name_str));
+                        }
+                    });
+                }
+
+                if let Some((authority, error)) = authority {
+                    let basic_error: Expr = parse_quote!(ErrorCode::MintAuthorityConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        {
+                            let authority: &Pubkey = &#authority;
+                            if #var_name.mint_authority() != Some(authority) {
+                                return Err(Error::from(#error).with_account(#name_str));
+                            }
+                        }
+                    });
+                }
+
+                if let Some((freeze_authority, error)) = freeze_authority.as_ref() {
+                    let basic_error: Expr = parse_quote!(ErrorCode::MintFreezeAuthorityConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        {
+                            let freeze_authority: &Pubkey = &#freeze_authority;
+                            if #var_name.freeze_authority() != Some(freeze_authority) {
+                                return Err(Error::from(#error).with_account(#name_str));
+                            }
+                        }
+                    });
+                }
+            }
+            AccountType::Multisig { ref signers } => {
+                if let Some(error) = signers {
+                    let basic_error: Expr = parse_quote!(ErrorCode::MultisigSignersConstraint);
+                    let error = error.as_ref().unwrap_or(&basic_error);
+                    token.extend(quote! {
+                        if !#var_name.is_signed_by(remaining) {
+                            return Err(Error::from(#error).with_account(#name_str));
                         }
                     });
                 }
             }
-            AccountType::Mint { .. } => {}
             AccountType::Other { ref targets, .. } => {
                 let basic_error: Expr = parse_quote!(ErrorCode::HasOneConstraint);
                 let targets = targets.iter().map(|(target, error)| {
@@ -318,7 +511,7 @@ impl AccountGenerator<'_> {
 
                     quote! {
                         if &#var_name.#target != #target.key() {
-                            return Err(#error.into());
+                            return Err(Error::from(#error).with_account(#name_str));
                         }
                     }
                 });
@@ -328,6 +521,85 @@ impl AccountGenerator<'_> {
         Ok(token)
     }
 
+    fn get_realloc(&self, ctx: &ReallocContext) -> Result<TokenStream, syn::Error> {
+        let name = &self.account.name;
+        let Some(ref size) = ctx.size else {
+            error!(
+                name,
+                "A size needs to be specified for the `realloc` constraint."
+            );
+        };
+        let Some(ref payer) = ctx.payer else {
+            error!(
+                name,
+                "A payer needs to be specified with `realloc::payer` for the `realloc` constraint."
+            );
+        };
+
+        Ok(quote!(ReallocAccount::realloc(&#name, #size, &#payer, &rent)?;))
+    }
+
+    fn get_checks(&self) -> TokenStream {
+        let mut token = TokenStream::new();
+        let name = &self.account.name;
+        let name_str = name.to_string();
+
+        if let Some(ConstraintAddress { address, error }) = &self.address {
+            let basic_error: Expr = parse_quote!(ErrorCode::AddressConstraint);
+            let error = error.as_ref().unwrap_or(&basic_error);
+            token.extend(quote! {
+                if #name.key() != &#address {
+                    return Err(Error::from(#error).with_account(#name_str));
+                }
+            });
+        }
+
+        if let Some(ConstraintOwner { owner, error }) = &self.owner {
+            let basic_error: Expr = parse_quote!(ErrorCode::OwnerConstraint);
+            let error = error.as_ref().unwrap_or(&basic_error);
+            token.extend(quote! {
+                if !#name.is_owned_by(&#owner) {
+                    return Err(Error::from(#error).with_account(#name_str));
+                }
+            });
+        }
+
+        let basic_error: Expr = parse_quote!(ErrorCode::ConstraintViolated);
+        let customs = self.customs.iter().map(|ConstraintCustom { expr, error }| {
+            let error = error.as_ref().unwrap_or(&basic_error);
+            quote! {
+                if !(#expr) {
+                    return Err(Error::from(#error).with_account(#name_str));
+                }
+            }
+        });
+        token.extend(customs);
+
+        token
+    }
+
+    pub fn close_token(&self) -> Option<TokenStream> {
+        let name = &self.account.name;
+        let destination = self.close.as_ref()?;
+
+        let close_token = quote! {
+            CloseAccount::close(
+                &<Mut<UncheckedAccount> as FromAccountInfo>::try_from_info(#name)?,
+                &<Mut<UncheckedAccount> as FromAccountInfo>::try_from_info(#destination)?,
+            )?;
+        };
+
+        if self.account.meta.is_optional {
+            Some(quote! {
+                if #name.key() != program_id {
+                    #close_token
+                }
+            })
+        } else {
+            Some(close_token)
+        }
+    }
+
     fn needs_return_bump(&self) -> bool {
         let has_pda = self.pda.is_some();
         let pda_has_no_bump = self
@@ -393,9 +665,17 @@ impl AccountGenerator<'_> {
             } else {
                 quote!(#init_account_token)
             }
+        } else if let Some(ref realloc_ctx) = self.realloc {
+            let account_token = self.account_token()?;
+            let realloc_token = self.get_realloc(realloc_ctx)?;
+            quote! {
+                #account_token
+                #realloc_token
+            }
         } else {
             self.account_token()?
         };
+        let checks_token = self.get_checks();
 
         if self.account.meta.is_optional {
             token.extend(quote! {
@@ -403,17 +683,99 @@ impl AccountGenerator<'_> {
                     None
                 } else {
                     #account_checks_token
+                    #checks_token
                     Some(#return_ty)
                 };
             });
         } else {
             token.extend(account_checks_token);
+            token.extend(checks_token);
         };
 
         Ok(token)
     }
 }
 
+fn mint_extension_token(extension: &ConstraintMintExtension) -> TokenStream {
+    let option = |expr: &Option<Expr>| match expr {
+        Some(expr) => quote!(Some(#expr)),
+        None => quote!(None),
+    };
+
+    match extension {
+        ConstraintMintExtension::CloseAuthority { authority } => {
+            let close_authority = option(authority);
+            quote!(MintExtension::MintCloseAuthority { close_authority: #close_authority })
+        }
+        ConstraintMintExtension::TransferFee {
+            config_authority,
+            withdraw_withheld_authority,
+            basis_points,
+            maximum_fee,
+        } => {
+            let config_authority = option(config_authority);
+            let withdraw_withheld_authority = option(withdraw_withheld_authority);
+            quote! {
+                MintExtension::TransferFee {
+                    config_authority: #config_authority,
+                    withdraw_withheld_authority: #withdraw_withheld_authority,
+                    basis_points: #basis_points,
+                    maximum_fee: #maximum_fee,
+                }
+            }
+        }
+        ConstraintMintExtension::DefaultAccountState { state } => {
+            quote!(MintExtension::DefaultAccountState { state: #state })
+        }
+        ConstraintMintExtension::NonTransferable => quote!(MintExtension::NonTransferable),
+        ConstraintMintExtension::InterestBearing {
+            rate_authority,
+            rate,
+        } => {
+            let rate_authority = option(rate_authority);
+            quote!(MintExtension::InterestBearing { rate_authority: #rate_authority, rate: #rate })
+        }
+        ConstraintMintExtension::PermanentDelegate { delegate } => {
+            quote!(MintExtension::PermanentDelegate { delegate: #delegate })
+        }
+        ConstraintMintExtension::TransferHook {
+            authority,
+            program_id,
+        } => {
+            let authority = option(authority);
+            let program_id = option(program_id);
+            quote!(MintExtension::TransferHook { authority: #authority, program_id: #program_id })
+        }
+        ConstraintMintExtension::MetadataPointer {
+            authority,
+            metadata_address,
+        } => {
+            let authority = option(authority);
+            let metadata_address = option(metadata_address);
+            quote!(MintExtension::MetadataPointer { authority: #authority, metadata_address: #metadata_address })
+        }
+        ConstraintMintExtension::GroupPointer {
+            authority,
+            group_address,
+        } => {
+            let authority = option(authority);
+            let group_address = option(group_address);
+            quote!(MintExtension::GroupPointer { authority: #authority, group_address: #group_address })
+        }
+        ConstraintMintExtension::GroupMemberPointer {
+            authority,
+            member_address,
+        } => {
+            let authority = option(authority);
+            let member_address = option(member_address);
+            quote!(MintExtension::GroupMemberPointer { authority: #authority, member_address: #member_address })
+        }
+        ConstraintMintExtension::Pausable { authority } => {
+            quote!(MintExtension::Pausable { authority: #authority })
+        }
+    }
+}
+
 /*
 ConstraintAssociatedToken::Mint(ident) => {
     if let Some(seeds) = self.seeds.as_mut() {
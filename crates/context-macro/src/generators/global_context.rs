@@ -1,63 +1,84 @@
 use {
     crate::{
-        generators::account::{AccountGenerator, AccountType, InitContext, PdaContext},
+        generators::account::{
+            AccountGenerator, AccountType, InitContext, MintContext, PdaContext,
+            TokenAccountContext,
+        },
         ParsingContext,
     },
     proc_macro2::TokenStream,
     quote::{format_ident, quote},
     std::collections::HashSet,
-    syn::{parse_quote, Ident, ItemStruct},
+    syn::{parse_quote, Ident, ItemStruct, Type},
     typhoon_syn::{
-        constraints::{Constraint, ConstraintAssociatedToken, ConstraintMint, ConstraintToken},
-        error, Argument, Arguments,
+        constraints::{
+            Constraint, ConstraintAssociatedToken, ConstraintMint, ConstraintMultisig,
+            ConstraintRealloc, ConstraintToken,
+        },
+        error, Argument, Arguments, NestedContext,
     },
 };
 
 pub struct GlobalContext<'a> {
     pub need_rent: bool,
+    /// The accounts following the top-level context are read, by `multisig::signers`.
+    pub need_remaining: bool,
     pub accounts: Vec<AccountGenerator<'a>>,
     pub bumps: HashSet<String>,
     pub program_checks: HashSet<String>,
 }
 
 impl<'a> GlobalContext<'a> {
+    /// Name of the arguments struct, generated from the `#[args(..)]` values and the arguments of
+    /// the flattened contexts, which are merged after the ones of the context.
     pub fn generate_args(&self, context: &ParsingContext) -> Option<(Ident, Option<TokenStream>)> {
-        let args = context.args.as_ref()?;
-
-        let result = match args {
-            Arguments::Struct(name) => (name.clone(), None),
-            Arguments::Values(args) => {
-                let struct_name = format_ident!("{}Args", context.item_struct.ident);
-                let fields = args
-                    .iter()
-                    .map(|Argument { name, ty }: &Argument| quote!(pub #name: #ty));
-
-                let generated_struct = quote! {
-                    #[derive(Debug, PartialEq, bytemuck::AnyBitPattern, bytemuck::NoUninit, Copy, Clone)]
-                    #[repr(C)]
-                    pub struct #struct_name {
-                        #(#fields),*
-                    }
-                };
+        let args = match &context.args {
+            Some(Arguments::Struct(name)) => return Some((name.clone(), None)),
+            Some(Arguments::Values(args)) => args.as_slice(),
+            None if !context.nested.is_empty() => &[],
+            None => return None,
+        };
 
-                (struct_name, Some(generated_struct))
+        let struct_name = format_ident!("{}Args", context.item_struct.ident);
+        let fields = args
+            .iter()
+            .map(|Argument { name, ty }: &Argument| quote!(pub #name: #ty));
+        let nested_fields = context.nested.iter().map(|nested| {
+            let name = &nested.name;
+            let ty = flattened_type(nested, format_ident!("Args"));
+            quote!(pub #name: #ty)
+        });
+
+        let generated_struct = quote! {
+            #[derive(Debug, PartialEq, bytemuck::AnyBitPattern, bytemuck::NoUninit, Copy, Clone)]
+            #[repr(C)]
+            pub struct #struct_name {
+                #(#fields,)*
+                #(#nested_fields,)*
             }
         };
 
-        Some(result)
+        Some((struct_name, Some(generated_struct)))
     }
 
+    /// Bumps of the context, the ones of the flattened contexts are stored under their name.
     pub fn generate_bumps(&self, context: &ParsingContext) -> Option<(ItemStruct, TokenStream)> {
-        if self.bumps.is_empty() {
+        if self.bumps.is_empty() && context.nested.is_empty() {
             return None;
         }
 
         let struct_name = format_ident!("{}Bumps", context.item_struct.ident);
         let struct_fields = self.bumps.iter().map(|el| format_ident!("{}", el));
+        let nested_fields = context.nested.iter().map(|nested| {
+            let name = &nested.name;
+            let ty = flattened_type(nested, format_ident!("Bumps"));
+            quote!(pub #name: #ty)
+        });
         let bumps_struct = parse_quote! {
-            #[derive(Debug, PartialEq)]
+            #[derive(Debug, PartialEq, Clone, Copy)]
             pub struct #struct_name {
                 #(pub #struct_fields: u8,)*
+                #(#nested_fields,)*
             }
         };
 
@@ -66,9 +87,14 @@ impl<'a> GlobalContext<'a> {
             let bump_ident = format_ident!("{n}_bump");
             quote!(#name: #bump_ident)
         });
+        let nested_assign_fields = context.nested.iter().map(|nested| {
+            let name = &nested.name;
+            quote!(#name: FlattenContext::bumps(&#name))
+        });
         let bumps_var = quote! {
             let bumps = #struct_name {
-                #(#assign_fields),*
+                #(#assign_fields,)*
+                #(#nested_assign_fields,)*
             };
         };
 
@@ -77,6 +103,7 @@ impl<'a> GlobalContext<'a> {
 
     pub fn from_parsing_context(context: &'a ParsingContext) -> Result<Self, syn::Error> {
         let mut need_rent = false;
+        let mut need_remaining = false;
         let mut accounts: Vec<AccountGenerator<'_>> = Vec::new();
         let mut bumps = HashSet::new();
         let mut program_checks = HashSet::new();
@@ -85,16 +112,9 @@ impl<'a> GlobalContext<'a> {
         //TODO optimize sorting etc..
         for account in &context.accounts {
             let account_ty = match account.inner_ty.to_string().as_str() {
-                "TokenAccount" => AccountType::TokenAccount {
-                    is_ata: false,
-                    mint: None,
-                    owner: None,
-                },
-                "Mint" => AccountType::Mint {
-                    authority: None,
-                    decimals: None,
-                    freeze_authority: Box::new(None),
-                },
+                "TokenAccount" => AccountType::TokenAccount(Box::default()),
+                "Mint" => AccountType::Mint(Box::default()),
+                "Multisig" => AccountType::Multisig { signers: None },
                 _ => AccountType::Other {
                     space: None,
                     targets: vec![],
@@ -103,6 +123,19 @@ impl<'a> GlobalContext<'a> {
             let mut generator = AccountGenerator::new(account, account_ty);
             let name = &account.name;
 
+            let is_modified = account.constraints.0.iter().any(|c| {
+                matches!(
+                    c,
+                    Constraint::Init(_)
+                        | Constraint::InitIfNeeded(_)
+                        | Constraint::Close(_)
+                        | Constraint::Realloc(_)
+                )
+            });
+            if account.is_sysvar() && (account.meta.is_mutable || is_modified) {
+                error!(name, "Sysvar accounts are read-only.");
+            }
+
             for constraint in &account.constraints.0 {
                 match constraint {
                     Constraint::Init(_) => {
@@ -116,7 +149,10 @@ impl<'a> GlobalContext<'a> {
                     }
                     Constraint::Payer(constraint_payer) => {
                         if let Some(init_ctx) = &mut generator.init {
-                            if matches!(generator.account_ty, AccountType::Other { .. }) {
+                            if matches!(
+                                generator.account_ty,
+                                AccountType::Other { .. } | AccountType::Mint(_)
+                            ) {
                                 need_rent = true;
                             }
                             init_ctx.payer = Some(constraint_payer.target.to_owned());
@@ -151,6 +187,7 @@ impl<'a> GlobalContext<'a> {
                             bump: None,
                             is_seeded: true,
                             program_id: None,
+                            error: None,
                         });
                     }
                     Constraint::Seeds(constraint_seeds) => {
@@ -163,6 +200,7 @@ impl<'a> GlobalContext<'a> {
                             bump: None,
                             is_seeded: false,
                             program_id: None,
+                            error: constraint_seeds.error.to_owned(),
                         });
                     }
                     Constraint::Bump(constraint_bump) => {
@@ -212,13 +250,23 @@ impl<'a> GlobalContext<'a> {
                         }
                     }
                     Constraint::Token(constraint_token) => {
-                        if let AccountType::TokenAccount {
-                            is_ata,
-                            mint,
-                            owner,
-                        } = &mut generator.account_ty
-                        {
-                            if *is_ata {
+                        if let AccountType::TokenAccount(token) = &mut generator.account_ty {
+                            let TokenAccountContext {
+                                is_ata,
+                                mint,
+                                owner,
+                                delegate,
+                                close_authority,
+                                is_frozen,
+                                native,
+                                ..
+                            } = token.as_mut();
+                            if *is_ata
+                                && matches!(
+                                    constraint_token,
+                                    ConstraintToken::Mint(..) | ConstraintToken::Owner(..)
+                                )
+                            {
                                 error!(name, "`associated_token` is already defined.");
                             }
 
@@ -227,10 +275,22 @@ impl<'a> GlobalContext<'a> {
                             }
 
                             match constraint_token {
-                                ConstraintToken::Mint(ident) => {
-                                    *mint = Some(ident.to_owned());
+                                ConstraintToken::Mint(ident, error) => {
+                                    *mint = Some((ident.to_owned(), error.to_owned()));
+                                }
+                                ConstraintToken::Owner(expr, error) => {
+                                    *owner = Some((expr.to_owned(), error.to_owned()))
+                                }
+                                ConstraintToken::Delegate(expr, error) => {
+                                    *delegate = Some((expr.to_owned(), error.to_owned()))
                                 }
-                                ConstraintToken::Owner(expr) => *owner = Some(expr.to_owned()),
+                                ConstraintToken::CloseAuthority(expr, error) => {
+                                    *close_authority = Some((expr.to_owned(), error.to_owned()))
+                                }
+                                ConstraintToken::IsFrozen(expr, error) => {
+                                    *is_frozen = Some((expr.to_owned(), error.to_owned()))
+                                }
+                                ConstraintToken::Native(error) => *native = Some(error.to_owned()),
                             }
                         } else {
                             error!(
@@ -240,21 +300,39 @@ impl<'a> GlobalContext<'a> {
                         }
                     }
                     Constraint::Mint(constraint_mint) => {
-                        if let AccountType::Mint {
-                            decimals,
-                            authority,
-                            freeze_authority,
-                        } = &mut generator.account_ty
-                        {
-                            states.insert(name.to_string());
+                        if let AccountType::Mint(mint) = &mut generator.account_ty {
+                            let MintContext {
+                                decimals,
+                                authority,
+                                freeze_authority,
+                                token_program,
+                                extensions,
+                            } = mint.as_mut();
+                            if !matches!(
+                                constraint_mint,
+                                ConstraintMint::TokenProgram(_) | ConstraintMint::Extension(_)
+                            ) {
+                                states.insert(name.to_string());
+                            }
 
                             match constraint_mint {
-                                ConstraintMint::Authority(expr) => {
-                                    *authority = Some(expr.to_owned())
+                                ConstraintMint::Authority(expr, error) => {
+                                    *authority = Some((expr.to_owned(), error.to_owned()))
+                                }
+                                ConstraintMint::Decimals(expr, error) => {
+                                    *decimals = Some((expr.to_owned(), error.to_owned()))
                                 }
-                                ConstraintMint::Decimals(expr) => *decimals = Some(expr.to_owned()),
-                                ConstraintMint::FreezeAuthority(expr) => {
-                                    *freeze_authority = Box::new(Some(expr.to_owned()))
+                                ConstraintMint::FreezeAuthority(expr, error) => {
+                                    *freeze_authority = Some((expr.to_owned(), error.to_owned()))
+                                }
+                                ConstraintMint::TokenProgram(ident) => {
+                                    if token_program.is_some() {
+                                        error!(name, "`mint::token_program` is already defined.");
+                                    }
+                                    *token_program = Some(ident.to_owned())
+                                }
+                                ConstraintMint::Extension(extension) => {
+                                    extensions.push(*extension.to_owned())
                                 }
                             }
                         } else {
@@ -265,21 +343,37 @@ impl<'a> GlobalContext<'a> {
                         }
                     }
                     Constraint::AssociatedToken(constraint_associated_token) => {
-                        if let AccountType::TokenAccount {
-                            mint,
-                            owner,
-                            is_ata,
-                        } = &mut generator.account_ty
-                        {
+                        if let AccountType::TokenAccount(token) = &mut generator.account_ty {
+                            let TokenAccountContext {
+                                mint,
+                                owner,
+                                is_ata,
+                                token_program,
+                                ..
+                            } = token.as_mut();
                             *is_ata = true;
-                            states.insert(name.to_string());
+                            if !matches!(
+                                constraint_associated_token,
+                                ConstraintAssociatedToken::TokenProgram(_)
+                            ) {
+                                states.insert(name.to_string());
+                            }
 
                             match constraint_associated_token {
-                                ConstraintAssociatedToken::Mint(ident) => {
-                                    *mint = Some(ident.to_owned());
+                                ConstraintAssociatedToken::Mint(ident, error) => {
+                                    *mint = Some((ident.to_owned(), error.to_owned()));
+                                }
+                                ConstraintAssociatedToken::Authority(ident, error) => {
+                                    *owner = Some((parse_quote!(#ident), error.to_owned()));
                                 }
-                                ConstraintAssociatedToken::Authority(ident) => {
-                                    *owner = Some(parse_quote!(#ident));
+                                ConstraintAssociatedToken::TokenProgram(ident) => {
+                                    if token_program.is_some() {
+                                        error!(
+                                            name,
+                                            "`associated_token::token_program` is already defined."
+                                        );
+                                    }
+                                    *token_program = Some(ident.to_owned());
                                 }
                             }
                         } else {
@@ -302,9 +396,116 @@ impl<'a> GlobalContext<'a> {
                             payer: None,
                         })
                     }
+                    Constraint::Close(constraint_close) => {
+                        if !account.meta.is_mutable {
+                            error!(name, "The account needs to be mutable to be closed.");
+                        }
+
+                        let destination = &constraint_close.destination;
+                        if destination == name {
+                            error!(destination, "An account cannot be closed into itself.");
+                        }
+
+                        let Some(destination_account) =
+                            context.accounts.iter().find(|el| &el.name == destination)
+                        else {
+                            error!(
+                                destination,
+                                "The `close` destination needs to be an account of the context."
+                            );
+                        };
+
+                        if !destination_account.meta.is_mutable
+                            || destination_account.meta.is_optional
+                        {
+                            error!(
+                                destination,
+                                "The `close` destination needs to be mutable and not optional."
+                            );
+                        }
+
+                        generator.close = Some(destination.to_owned());
+                    }
+                    Constraint::Realloc(constraint_realloc) => {
+                        if !account.meta.is_mutable {
+                            error!(name, "The account needs to be mutable to be reallocated.");
+                        }
+
+                        need_rent = true;
+                        let realloc_ctx = generator.realloc.get_or_insert_with(Default::default);
+                        match constraint_realloc {
+                            ConstraintRealloc::Size(expr) => {
+                                realloc_ctx.size = Some(expr.to_owned())
+                            }
+                            ConstraintRealloc::Payer(ident) => {
+                                realloc_ctx.payer = Some(ident.to_owned())
+                            }
+                            // `resize` always zero-initializes the new bytes.
+                            ConstraintRealloc::Zero => {}
+                        }
+                    }
+                    Constraint::Custom(constraint_custom) => {
+                        generator.customs.push(constraint_custom.to_owned());
+                    }
+                    Constraint::Address(constraint_address) => {
+                        if generator.address.is_some() {
+                            error!(name, "`address` is already defined.");
+                        }
+                        generator.address = Some(constraint_address.to_owned());
+                    }
+                    Constraint::Owner(constraint_owner) => {
+                        if generator.owner.is_some() {
+                            error!(name, "`owner` is already defined.");
+                        }
+                        generator.owner = Some(constraint_owner.to_owned());
+                    }
+                    Constraint::Multisig(constraint_multisig) => {
+                        if let AccountType::Multisig { signers } = &mut generator.account_ty {
+                            states.insert(name.to_string());
+
+                            match constraint_multisig {
+                                ConstraintMultisig::Signers(error) => {
+                                    need_remaining = true;
+                                    *signers = Some(error.to_owned())
+                                }
+                            }
+                        } else {
+                            error!(
+                                name,
+                                "`multisig` can only be used with the `Multisig` type."
+                            );
+                        }
+                    }
+                }
+            }
+
+            if let (
+                Some(InitContext {
+                    is_init_if_needed: false,
+                    ..
+                }),
+                AccountType::TokenAccount(token),
+            ) = (&generator.init, &generator.account_ty)
+            {
+                if token.delegate.is_some()
+                    || token.close_authority.is_some()
+                    || token.is_frozen.is_some()
+                    || token.native.is_some()
+                {
+                    error!(
+                        name,
+                        "`token::delegate`, `token::close_authority`, `token::is_frozen` and `token::native` cannot be used with the `init` constraint."
+                    );
                 }
             }
 
+            if generator.init.is_some() && generator.realloc.is_some() {
+                error!(
+                    name,
+                    "`realloc` cannot be used with `init` or `init_if_needed` constraint."
+                );
+            }
+
             for program in generator.needs_programs() {
                 program_checks.insert(program);
             }
@@ -321,9 +522,16 @@ impl<'a> GlobalContext<'a> {
 
         Ok(GlobalContext {
             need_rent,
+            need_remaining,
             accounts,
             bumps,
             program_checks,
         })
     }
 }
+
+/// Associated type of a flattened context, usable outside of the lifetimes of the handler.
+fn flattened_type(nested: &NestedContext, assoc: Ident) -> Type {
+    let context = &nested.context;
+    parse_quote!(<#context<'static> as FlattenContext<'static, 'static, 'static>>::#assoc)
+}
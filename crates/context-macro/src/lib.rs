@@ -5,16 +5,21 @@ use {
     proc_macro::TokenStream,
     proc_macro2::TokenStream as TokenStream2,
     quote::{format_ident, quote, ToTokens},
-    sorter::sort_accounts,
+    rewriter::FieldRewriter,
+    sorter::{external_dependencies, sort_accounts},
+    std::collections::HashSet,
     syn::{
-        parse_macro_input, parse_quote, visit_mut::VisitMut, Attribute, Field, Ident, ItemStruct,
+        parse_macro_input, parse_quote, punctuated::Punctuated, visit_mut::VisitMut, Attribute,
+        Expr, Field, Ident, ItemStruct, Token,
     },
+    typhoon_syn::{utils::SeedsExpr, NestedContext},
 };
 
 mod context;
 mod generators;
 mod injector;
 mod remover;
+mod rewriter;
 mod sorter;
 mod visitor;
 
@@ -37,12 +42,30 @@ struct TokenGenerator {
     bumps: Option<BumpsStruct>,
     args: Option<(Ident, Option<TokenStream2>)>,
     needs_rent: bool,
+    needs_remaining: bool,
+    closes: Vec<TokenStream2>,
+    close_accounts: HashSet<String>,
+    close_optional: bool,
+    nested: Vec<NestedContext>,
+    event_cpi: bool,
+    signer_seeds: Vec<(Ident, Punctuated<Expr, Token![,]>)>,
+    /// Idents of the constraints bound to the accounts of the flattened contexts.
+    flattened_refs: Vec<Ident>,
 }
 
 impl TokenGenerator {
     pub fn new(mut context: ParsingContext) -> Result<Self, syn::Error> {
         sort_accounts(&mut context)?;
 
+        let flattened_refs = if context.nested.is_empty() {
+            Vec::new()
+        } else {
+            external_dependencies(&context)?
+                .iter()
+                .map(|name| format_ident!("{name}"))
+                .collect()
+        };
+
         let global_context = GlobalContext::from_parsing_context(&context)?;
 
         for program in &global_context.program_checks {
@@ -57,6 +80,43 @@ impl TokenGenerator {
         let bumps = global_context.generate_bumps(&context);
         let args = global_context.generate_args(&context);
 
+        let closes = global_context
+            .accounts
+            .iter()
+            .filter_map(|acc| acc.close_token())
+            .collect();
+        let close_optional = global_context
+            .accounts
+            .iter()
+            .any(|acc| acc.close.is_some() && acc.account.meta.is_optional);
+        let close_accounts = global_context
+            .accounts
+            .iter()
+            .filter_map(|acc| {
+                Some([
+                    acc.account.name.to_string(),
+                    acc.close.as_ref()?.to_string(),
+                ])
+            })
+            .flatten()
+            .collect();
+
+        let signer_seeds = global_context
+            .accounts
+            .iter()
+            .filter(|acc| global_context.bumps.contains(&acc.account.name.to_string()))
+            .filter_map(|acc| {
+                let pda = acc.pda.as_ref()?;
+                if pda.is_seeded || pda.program_id.is_some() {
+                    return None;
+                }
+                let Some(SeedsExpr::Punctuated(keys)) = &pda.keys else {
+                    return None;
+                };
+                Some((acc.account.name.clone(), keys.clone()))
+            })
+            .collect();
+
         let accounts_token = global_context
             .accounts
             .into_iter()
@@ -64,13 +124,66 @@ impl TokenGenerator {
             .collect::<Result<_, _>>()?;
 
         Ok(TokenGenerator {
+            event_cpi: context.event_cpi,
             needs_rent: global_context.need_rent,
+            needs_remaining: global_context.need_remaining,
             item_struct: context.item_struct,
             accounts_token,
             bumps,
             args,
+            closes,
+            close_accounts,
+            close_optional,
+            nested: context.nested,
+            signer_seeds,
+            flattened_refs,
         })
     }
+
+    /// Destructures `accounts` up to `rem`, the flattened contexts consume their accounts in place.
+    fn destructure_accounts(
+        &self,
+        patterns: &[TokenStream2],
+        nested_call: impl Fn(&NestedContext) -> TokenStream2,
+    ) -> TokenStream2 {
+        let not_enough_keys = quote!(return Err(ProgramError::NotEnoughAccountKeys.into()););
+        let mut tokens = TokenStream2::new();
+        let mut source = quote!(accounts);
+        let mut start = 0;
+
+        for nested in &self.nested {
+            let segment = &patterns[start..nested.index];
+            if !segment.is_empty() {
+                tokens.extend(
+                    quote!(let [#(#segment,)* rem @ ..] = #source else { #not_enough_keys };),
+                );
+            } else if start == 0 {
+                tokens.extend(quote!(let rem = *accounts;));
+            }
+
+            let call = nested_call(nested);
+            tokens.extend(quote! {
+                let mut rem = rem;
+                #call
+            });
+            source = quote!(rem);
+            start = nested.index;
+        }
+
+        let segment = &patterns[start..];
+        if !segment.is_empty() || self.nested.is_empty() {
+            tokens
+                .extend(quote!(let [#(#segment,)* rem @ ..] = #source else { #not_enough_keys };));
+        }
+
+        tokens
+    }
+}
+
+/// Name of the macro binding the accounts of a flattened context in the scope of the outer
+/// constraints, see [`TokenGenerator::flattened_refs`].
+fn binder_ident(context: &Ident) -> Ident {
+    format_ident!("__flatten_{context}")
 }
 
 impl ToTokens for TokenGenerator {
@@ -96,6 +209,11 @@ impl ToTokens for TokenGenerator {
             .iter()
             .filter_map(|f| f.ident.as_ref())
             .collect();
+        let account_list: Vec<&Ident> = name_list
+            .iter()
+            .filter(|name| self.nested.iter().all(|nested| &nested.name != **name))
+            .copied()
+            .collect();
         let accounts_token = &self.accounts_token;
         let (bumps_struct, bumps_var) = self.bumps.clone().unzip();
 
@@ -117,7 +235,12 @@ impl ToTokens for TokenGenerator {
             struct_fields.push(&args_ident);
             FieldInjector::new(args_field).visit_item_struct_mut(account_struct);
 
-            let args_assign = quote!(let Arg(args) = Arg::<#name>::from_entrypoint(program_id, accounts, instruction_data)?;);
+            let args_assign = quote! {
+                let args = match args {
+                    Some(args) => args,
+                    None => Arg::<#name>::from_entrypoint(program_id, accounts, instruction_data)?.0,
+                };
+            };
 
             (args_assign, args_struct)
         }).unzip();
@@ -126,19 +249,210 @@ impl ToTokens for TokenGenerator {
             .needs_rent
             .then_some(quote!(let rent = <Rent as Sysvar>::get()?;));
 
+        // Always generated, a context flattening this one relies on it to advance `accounts`.
+        let finalize = {
+            let closes = &self.closes;
+            let program_id = if self.close_optional || !self.nested.is_empty() {
+                format_ident!("program_id")
+            } else {
+                format_ident!("_program_id")
+            };
+            let finalize_list: Vec<TokenStream2> = account_list
+                .iter()
+                .map(|name| {
+                    if self.close_accounts.contains(&name.to_string()) {
+                        quote!(#name)
+                    } else {
+                        quote!(_)
+                    }
+                })
+                .collect();
+            let destructure = self.destructure_accounts(&finalize_list, |nested| {
+                let context = &nested.context;
+                quote!(<#context as HandlerContext>::finalize(program_id, &mut rem)?;)
+            });
+
+            quote! {
+                #[inline(always)]
+                fn finalize(
+                    #program_id: &Pubkey,
+                    accounts: &mut &'info [AccountInfo],
+                ) -> ProgramResult {
+                    #destructure
+
+                    #(#closes)*
+                    *accounts = rem;
+
+                    Ok(())
+                }
+            }
+        };
+
+        let impl_event_cpi = self.event_cpi.then(|| {
+            let (impl_generics, ty_generics, where_clause) =
+                self.item_struct.generics.split_for_impl();
+
+            quote! {
+                impl #impl_generics EventCpiContext for #name #ty_generics #where_clause {
+                    #[inline(always)]
+                    fn event_authority(&self) -> &AccountInfo {
+                        self.event_authority.as_ref()
+                    }
+
+                    #[inline(always)]
+                    fn event_authority_bump(&self) -> u8 {
+                        self.bumps.event_authority
+                    }
+
+                    #[inline(always)]
+                    fn event_program(&self) -> &AccountInfo {
+                        self.program.as_ref()
+                    }
+                }
+            }
+        });
+
+        let impl_signer_seeds = {
+            let fields: HashSet<String> = struct_fields.iter().map(|f| f.to_string()).collect();
+            let methods: Vec<TokenStream2> = self
+                .signer_seeds
+                .iter()
+                .filter_map(|(account, keys)| {
+                    let mut keys = keys.clone();
+                    let mut rewriter = FieldRewriter::new(&fields);
+                    for key in keys.iter_mut() {
+                        rewriter.visit_expr_mut(key);
+                    }
+                    if rewriter.unresolved {
+                        return None;
+                    }
+
+                    let method = format_ident!("{account}_signer_seeds");
+                    let keys = keys.iter();
+                    let len = keys.len() + 1;
+                    let doc = format!(
+                        " Signer seeds of `{account}`, derived from its `seeds` and `bumps.{account}`."
+                    );
+                    Some(quote! {
+                        #[doc = #doc]
+                        #[inline(always)]
+                        pub fn #method(&self) -> [instruction::Seed<'_>; #len] {
+                            seeds!(#(#keys,)* core::slice::from_ref(&self.bumps.#account))
+                        }
+                    })
+                })
+                .collect();
+
+            (!methods.is_empty()).then(|| {
+                let (impl_generics, ty_generics, where_clause) =
+                    self.item_struct.generics.split_for_impl();
+
+                quote! {
+                    impl #impl_generics #name #ty_generics #where_clause {
+                        #(#methods)*
+                    }
+                }
+            })
+        };
+
+        let account_patterns: Vec<TokenStream2> =
+            account_list.iter().map(|name| quote!(#name)).collect();
+        let merged_args = matches!(self.args, Some((_, Some(_))));
+        let destructure = self.destructure_accounts(&account_patterns, |nested| {
+            let name = &nested.name;
+            let context = &nested.context;
+            let args = if merged_args {
+                quote!(Some(&args.#name))
+            } else {
+                quote!(None)
+            };
+            quote! {
+                let #name = <#context as FlattenContext>::from_flattened(program_id, &mut rem, remaining, #args, instruction_data)?;
+            }
+        });
+        let flattened_refs = &self.flattened_refs;
+        let bind_flattened = self.nested.iter().map(|nested| {
+            let name = &nested.name;
+            let binder = binder_ident(&nested.context);
+            quote!(#(#binder!(#name, #flattened_refs => #flattened_refs);)*)
+        });
+        let (args_ty, args_param) = match &self.args {
+            Some((name, _)) => (quote!(#name), format_ident!("args")),
+            None => (quote!(()), format_ident!("_args")),
+        };
+        let (bumps_ty, bumps_value) = match &bumps_struct {
+            Some(bumps) => {
+                let name = &bumps.ident;
+                (quote!(#name), quote!(self.bumps))
+            }
+            None => (quote!(()), quote!()),
+        };
+        let remaining = if self.needs_remaining || !self.nested.is_empty() {
+            format_ident!("remaining")
+        } else {
+            format_ident!("_remaining")
+        };
+        let accounts_len = account_list.len();
+        let nested_contexts = self.nested.iter().map(|nested| &nested.context);
+        // The accounts following the context are read by `multisig::signers`.
+        let lazy_accounts_len = if self.needs_remaining {
+            quote!(None)
+        } else if self.nested.is_empty() {
+            quote!(Some(<Self as FlattenContext>::ACCOUNTS_LEN))
+        } else {
+            let nested_contexts = self.nested.iter().map(|nested| &nested.context);
+            quote! {
+                if #(<#nested_contexts as HandlerContext>::LAZY_ACCOUNTS_LEN.is_none())||* {
+                    None
+                } else {
+                    Some(<Self as FlattenContext>::ACCOUNTS_LEN)
+                }
+            }
+        };
+
         let impl_context = quote! {
             impl #impl_generics HandlerContext<'_, 'info, 'c> for #name #ty_generics #where_clause {
+                const LAZY_ACCOUNTS_LEN: Option<usize> = #lazy_accounts_len;
+
                 #[inline(always)]
                 fn from_entrypoint(
                     program_id: &Pubkey,
                     accounts: &mut &'info [AccountInfo],
                     instruction_data: &mut &'c [u8],
                 ) -> ProgramResult<Self> {
-                    let [#(#name_list,)* rem @ ..] = accounts else {
-                        return Err(ProgramError::NotEnoughAccountKeys.into());
-                    };
+                    let remaining = accounts
+                        .get(<Self as FlattenContext>::ACCOUNTS_LEN..)
+                        .unwrap_or_default();
 
+                    Self::from_flattened(program_id, accounts, remaining, None, instruction_data)
+                }
+
+                #finalize
+            }
+
+            impl #impl_generics FlattenContext<'_, 'info, 'c> for #name #ty_generics #where_clause {
+                const ACCOUNTS_LEN: usize = #accounts_len #(+ <#nested_contexts as FlattenContext>::ACCOUNTS_LEN)*;
+
+                type Bumps = #bumps_ty;
+
+                type Args = #args_ty;
+
+                #[inline(always)]
+                fn bumps(&self) -> Self::Bumps {
+                    #bumps_value
+                }
+
+                #[inline(always)]
+                fn from_flattened(
+                    program_id: &Pubkey,
+                    accounts: &mut &'info [AccountInfo],
+                    #remaining: &'info [AccountInfo],
+                    #args_param: Option<&'c Self::Args>,
+                    instruction_data: &mut &'c [u8],
+                ) -> ProgramResult<Self> {
                     #args_assign
+                    #destructure
+                    #(#bind_flattened)*
                     #rent
 
                     #(#accounts_token)*
@@ -151,6 +465,10 @@ impl ToTokens for TokenGenerator {
             }
 
             impl #impl_generics Context for #name #ty_generics #where_clause {}
+
+            #impl_event_cpi
+
+            #impl_signer_seeds
         };
 
         let doc = prettyplease::unparse(
@@ -172,6 +490,36 @@ impl ToTokens for TokenGenerator {
 
         account_struct.attrs.append(&mut doc_attrs);
 
+        let binder = {
+            let binder = binder_ident(name);
+            let fields = &account_list;
+            let nested_binders = self.nested.iter().map(|nested| {
+                let name = &nested.name;
+                let binder = binder_ident(&nested.context);
+                quote!(#binder!($ctx.#name, $field => $name);)
+            });
+
+            quote! {
+                #[doc(hidden)]
+                #[allow(unused_macros)]
+                macro_rules! #binder {
+                    #(
+                        ($ctx:expr, #fields => $name:ident) => {
+                            #[allow(unused_variables)]
+                            let $name = &$ctx.#fields;
+                        };
+                    )*
+                    ($ctx:expr, $field:ident => $name:ident) => {
+                        #(#nested_binders)*
+                    };
+                }
+
+                #[doc(hidden)]
+                #[allow(unused_imports)]
+                pub(crate) use #binder;
+            }
+        };
+
         let expanded = quote! {
             #bumps_struct
             #args_struct
@@ -180,6 +528,7 @@ impl ToTokens for TokenGenerator {
 
             #impl_context
 
+            #binder
         };
         expanded.to_tokens(tokens);
     }
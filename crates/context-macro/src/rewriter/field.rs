@@ -0,0 +1,38 @@
+use {
+    std::collections::HashSet,
+    syn::{parse_quote, visit_mut::VisitMut, Expr},
+};
+
+/// Rewrites the paths pointing to a context field into `self.<field>`.
+pub struct FieldRewriter<'a> {
+    fields: &'a HashSet<String>,
+    pub unresolved: bool,
+}
+
+impl<'a> FieldRewriter<'a> {
+    pub fn new(fields: &'a HashSet<String>) -> Self {
+        FieldRewriter {
+            fields,
+            unresolved: false,
+        }
+    }
+}
+
+impl VisitMut for FieldRewriter<'_> {
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        let Expr::Path(expr_path) = i else {
+            return syn::visit_mut::visit_expr_mut(self, i);
+        };
+
+        let Some(ident) = expr_path.path.get_ident() else {
+            return;
+        };
+
+        let name = ident.to_string();
+        if self.fields.contains(&name) {
+            *i = parse_quote!(self.#ident);
+        } else if name.starts_with(|c: char| c.is_lowercase()) {
+            self.unresolved = true;
+        }
+    }
+}
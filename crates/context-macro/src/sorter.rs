@@ -1,10 +1,11 @@
 use {
     crate::{context::ParsingContext, visitor::ContextVisitor},
     std::collections::HashMap,
+    syn::{visit::Visit, ExprPath},
     typhoon_syn::{
         constraints::{
-            ConstraintAssociatedToken, ConstraintBump, ConstraintHasOne, ConstraintPayer,
-            ConstraintToken,
+            ConstraintAssociatedToken, ConstraintBump, ConstraintCustom, ConstraintHasOne,
+            ConstraintPayer, ConstraintRealloc, ConstraintToken,
         },
         InstructionAccount,
     },
@@ -28,10 +29,20 @@ impl DependencyLinker {
     fn extract_dependencies(account: &InstructionAccount) -> Result<Vec<String>, syn::Error> {
         let mut linker = Self::new();
         linker.visit_account(account)?;
+        let name = account.name.to_string();
+        linker.dependencies.retain(|el| el != &name);
         Ok(linker.dependencies)
     }
 }
 
+impl Visit<'_> for DependencyLinker {
+    fn visit_expr_path(&mut self, expr_path: &ExprPath) {
+        if let Some(ident) = expr_path.path.get_ident() {
+            self.add_dependency(ident);
+        }
+    }
+}
+
 impl ContextVisitor for DependencyLinker {
     fn visit_payer(&mut self, constraint: &ConstraintPayer) -> Result<(), syn::Error> {
         self.add_dependency(&constraint.target);
@@ -48,7 +59,7 @@ impl ContextVisitor for DependencyLinker {
     }
 
     fn visit_token(&mut self, constraint: &ConstraintToken) -> Result<(), syn::Error> {
-        if let ConstraintToken::Mint(ident) = constraint {
+        if let ConstraintToken::Mint(ident, _) = constraint {
             self.add_dependency(ident)
         }
         Ok(())
@@ -59,8 +70,9 @@ impl ContextVisitor for DependencyLinker {
         constraint: &ConstraintAssociatedToken,
     ) -> Result<(), syn::Error> {
         match constraint {
-            ConstraintAssociatedToken::Mint(ident) => self.add_dependency(ident),
-            ConstraintAssociatedToken::Authority(ident) => self.add_dependency(ident),
+            ConstraintAssociatedToken::Mint(ident, _) => self.add_dependency(ident),
+            ConstraintAssociatedToken::Authority(ident, _) => self.add_dependency(ident),
+            ConstraintAssociatedToken::TokenProgram(ident) => self.add_dependency(ident),
         }
         Ok(())
     }
@@ -69,6 +81,18 @@ impl ContextVisitor for DependencyLinker {
         self.add_dependency(&constraint.join_target);
         Ok(())
     }
+
+    fn visit_custom(&mut self, constraint: &ConstraintCustom) -> Result<(), syn::Error> {
+        Visit::visit_expr(self, &constraint.expr);
+        Ok(())
+    }
+
+    fn visit_realloc(&mut self, constraint: &ConstraintRealloc) -> Result<(), syn::Error> {
+        if let ConstraintRealloc::Payer(ident) = constraint {
+            self.add_dependency(ident)
+        }
+        Ok(())
+    }
 }
 
 pub fn sort_accounts(context: &mut ParsingContext) -> Result<(), syn::Error> {
@@ -165,3 +189,23 @@ pub fn sort_accounts(context: &mut ParsingContext) -> Result<(), syn::Error> {
 
     Ok(())
 }
+
+/// Idents referenced by the constraints which are not accounts of the context, the ones that are
+/// accounts of a flattened context are bound before the constraints are checked.
+pub fn external_dependencies(context: &ParsingContext) -> Result<Vec<String>, syn::Error> {
+    let mut external = Vec::new();
+    for account in &context.accounts {
+        for dependency in DependencyLinker::extract_dependencies(account)? {
+            let is_account = context.accounts.iter().any(|acc| acc.name == dependency);
+            if !is_account
+                && !["args", "bumps", "program_id"].contains(&dependency.as_str())
+                && dependency.starts_with(|c: char| c.is_lowercase())
+                && !external.contains(&dependency)
+            {
+                external.push(dependency);
+            }
+        }
+    }
+
+    Ok(external)
+}
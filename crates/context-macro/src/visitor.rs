@@ -31,6 +31,12 @@ pub trait ContextVisitor {
             Constraint::InitIfNeeded(contraint_init_if_needed) => {
                 self.visit_init_if_needed(contraint_init_if_needed)
             }
+            Constraint::Close(constraint_close) => self.visit_close(constraint_close),
+            Constraint::Realloc(constraint_realloc) => self.visit_realloc(constraint_realloc),
+            Constraint::Custom(constraint_custom) => self.visit_custom(constraint_custom),
+            Constraint::Address(constraint_address) => self.visit_address(constraint_address),
+            Constraint::Owner(constraint_owner) => self.visit_owner(constraint_owner),
+            Constraint::Multisig(constraint_multisig) => self.visit_multisig(constraint_multisig),
         }
     }
 
@@ -87,4 +93,28 @@ pub trait ContextVisitor {
     ) -> Result<(), syn::Error> {
         Ok(())
     }
+
+    fn visit_close(&mut self, _constraint: &ConstraintClose) -> Result<(), syn::Error> {
+        Ok(())
+    }
+
+    fn visit_realloc(&mut self, _constraint: &ConstraintRealloc) -> Result<(), syn::Error> {
+        Ok(())
+    }
+
+    fn visit_custom(&mut self, _constraint: &ConstraintCustom) -> Result<(), syn::Error> {
+        Ok(())
+    }
+
+    fn visit_address(&mut self, _constraint: &ConstraintAddress) -> Result<(), syn::Error> {
+        Ok(())
+    }
+
+    fn visit_owner(&mut self, _constraint: &ConstraintOwner) -> Result<(), syn::Error> {
+        Ok(())
+    }
+
+    fn visit_multisig(&mut self, _constraint: &ConstraintMultisig) -> Result<(), syn::Error> {
+        Ok(())
+    }
 }
@@ -0,0 +1,40 @@
+use {
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_token::{AtaTokenProgram, Mint, SplCreateToken, TokenAccount, TokenProgram},
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct CreateAtaContext {
+    pub payer: Mut<Signer>,
+    pub owner: UncheckedAccount,
+    pub mint: InterfaceAccount<Mint>,
+    #[constraint(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_2022_program
+    )]
+    pub token_account: Mut<InterfaceAccount<TokenAccount>>,
+    #[constraint(
+        associated_token::mint = mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_2022_program
+    )]
+    pub payer_token_account: InterfaceAccount<TokenAccount>,
+    pub token_2022_program: Interface<TokenProgram>,
+    pub ata_program: Program<AtaTokenProgram>,
+    pub system_program: Program<System>,
+}
+
+pub fn main() {}
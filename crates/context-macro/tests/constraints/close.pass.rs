@@ -0,0 +1,38 @@
+use {
+    bytemuck::{AnyBitPattern, NoUninit},
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    pinocchio_pubkey::declare_id,
+    typhoon_account_macro::*,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_utility_traits::CloseAccount,
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(NoUninit, AnyBitPattern, AccountState, Copy, Clone)]
+#[repr(C)]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[context]
+pub struct CloseContext {
+    pub authority: Signer,
+    pub destination: Mut<SystemAccount>,
+    #[constraint(
+        close = destination
+    )]
+    pub counter: Mut<Account<Counter>>,
+    #[constraint(
+        close = destination
+    )]
+    pub optional_counter: Option<Mut<Account<Counter>>>,
+}
+
+pub fn main() {}
@@ -0,0 +1,38 @@
+use {
+    bytemuck::{AnyBitPattern, NoUninit},
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    pinocchio_pubkey::declare_id,
+    typhoon_account_macro::*,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(NoUninit, AnyBitPattern, AccountState, Copy, Clone)]
+#[repr(C)]
+pub struct Counter {
+    pub count: u64,
+}
+
+pub fn below(counter: &Mut<Account<Counter>>, max: u64) -> bool {
+    counter.data().is_ok_and(|state| state.count < max)
+}
+
+#[context]
+#[args(max: u64)]
+pub struct Increment {
+    pub authority: Signer,
+    #[constraint(
+        constraint = authority.key() != counter.key(),
+        constraint::below(&counter, args.max) @ ProgramError::InvalidArgument
+    )]
+    pub counter: Mut<Account<Counter>>,
+}
+
+pub fn main() {}
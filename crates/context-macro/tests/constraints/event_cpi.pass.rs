@@ -0,0 +1,35 @@
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::{find_program_address, Pubkey},
+        seeds,
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_utility_traits::{EventCpiContext, EVENT_AUTHORITY_SEED},
+};
+
+mod instruction {
+    pub use pinocchio::instruction::Seed;
+}
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+#[event_cpi]
+pub struct EmitContext {
+    pub payer: Mut<Signer>,
+}
+
+fn assert_event_cpi<T: EventCpiContext>() {}
+
+pub fn main() {
+    assert_event_cpi::<EmitContext>();
+}
@@ -0,0 +1,150 @@
+use {
+    bytemuck::{AnyBitPattern, NoUninit},
+    core::mem::MaybeUninit,
+    pinocchio::{
+        account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE},
+        entrypoint::deserialize,
+        program_error::ProgramError,
+        pubkey::{Pubkey, *},
+        seeds,
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_account_macro::*,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_utility_traits::CloseAccount,
+};
+
+mod instruction {
+    pub use pinocchio::instruction::Seed;
+}
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(NoUninit, AnyBitPattern, AccountState, Copy, Clone)]
+#[repr(C)]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[context]
+#[args(fee: u64)]
+pub struct Common {
+    pub authority: Signer,
+    #[constraint(
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Mut<SystemAccount>,
+}
+
+#[context]
+pub struct Closer {
+    pub destination: Mut<SystemAccount>,
+    #[constraint(
+        close = destination
+    )]
+    pub counter: Mut<Account<Counter>>,
+}
+
+#[context]
+#[args(amount: u64)]
+pub struct Deposit {
+    #[flatten]
+    pub common: Common,
+    #[constraint(
+        has_one = authority
+    )]
+    pub config: Account<Config>,
+    #[flatten]
+    pub closer: Closer,
+}
+
+#[derive(NoUninit, AnyBitPattern, AccountState, Copy, Clone)]
+#[repr(C)]
+pub struct Config {
+    pub authority: Pubkey,
+}
+
+pub fn deposit(ctx: Deposit) -> ProgramResult<u64> {
+    let _vault: &AccountInfo = ctx.common.vault.as_ref();
+    let _authority: &AccountInfo = ctx.common.authority.as_ref();
+    let _counter: &AccountInfo = ctx.closer.counter.as_ref();
+    assert_eq!(ctx.bumps.common, ctx.common.bumps);
+
+    Ok(ctx.args.amount + ctx.args.common.fee + ctx.bumps.common.vault as u64)
+}
+
+#[context]
+pub struct Owners {
+    pub payer: Mut<Signer>,
+    pub config: UncheckedAccount,
+}
+
+#[context]
+pub struct Reset {
+    #[flatten]
+    pub owners: Owners,
+    pub destination: Mut<SystemAccount>,
+    #[constraint(
+        close = destination
+    )]
+    pub counter: Mut<Account<Counter>>,
+}
+
+pub fn reset(_ctx: Reset) -> ProgramResult {
+    Ok(())
+}
+
+/// Serializes the accounts the way the runtime does, all of them are writable signers.
+fn account_infos(accounts: &[(Pubkey, u64, &[u8])]) -> Vec<AccountInfo> {
+    let mut input = Vec::new();
+    input.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for (i, (owner, lamports, data)) in accounts.iter().enumerate() {
+        input.extend_from_slice(&[u8::MAX, 1, 1, 0, 0, 0, 0, 0]);
+        input.extend_from_slice(&[i as u8 + 1; 32]);
+        input.extend_from_slice(owner);
+        input.extend_from_slice(&lamports.to_le_bytes());
+        input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        input.extend_from_slice(data);
+        input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        input.resize(input.len().next_multiple_of(8), 0);
+        input.extend_from_slice(&u64::MAX.to_le_bytes());
+    }
+    input.extend_from_slice(&0u64.to_le_bytes());
+    input.extend_from_slice(&ID);
+
+    let buffer = Box::leak(vec![0u64; input.len().div_ceil(8)].into_boxed_slice());
+    let ptr = buffer.as_mut_ptr() as *mut u8;
+    unsafe { core::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+
+    let mut infos = [MaybeUninit::<AccountInfo>::uninit(); 8];
+    let (_, len, _) = unsafe { deserialize::<8>(ptr, &mut infos) };
+
+    infos[..len]
+        .iter()
+        .map(|info| unsafe { info.assume_init() })
+        .collect()
+}
+
+pub fn main() {
+    let counter = [Counter::DISCRIMINATOR, &[0; 8]].concat();
+    let accounts = account_infos(&[
+        (Pubkey::default(), 1, &[]),
+        (ID, 2, &[]),
+        (Pubkey::default(), 3, &[]),
+        (ID, 4, &counter),
+    ]);
+
+    // The nested context has no close, its finalize still needs to skip its accounts.
+    let result = Handler::call(reset, &ID, &mut accounts.as_slice(), &mut [].as_slice());
+    assert!(result.is_ok());
+
+    let lamports: Vec<u64> = accounts.iter().map(|acc| acc.lamports()).collect();
+    assert_eq!(lamports, [1, 2, 7, 0]);
+}
@@ -0,0 +1,28 @@
+use {
+    pinocchio::pubkey::Pubkey,
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context_macro::*,
+    typhoon_program_id_macro::program_id,
+};
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct InitContext {
+    pub payer: Mut<Signer>,
+    pub owner: UncheckedAccount,
+    pub mint: UncheckedAccount,
+    #[constraint(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::owner = owner,
+        token::delegate = owner
+    )]
+    pub token_acc: Mut<Signer<Account<TokenAccount>>>,
+    pub token_program: Program<TokenProgram>,
+    pub system_program: Program<System>,
+}
+
+pub fn main() {}
@@ -0,0 +1,50 @@
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_token::{extensions::MintExtension, Mint, SplCreateMint, TokenProgram},
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct CreateMintContext {
+    pub payer: Mut<Signer>,
+    pub authority: UncheckedAccount,
+    pub hook_program: UncheckedAccount,
+    #[constraint(
+        init,
+        payer = payer,
+        mint::decimals = 6,
+        mint::authority = authority.key(),
+        mint::token_program = token_program,
+        mint::extensions::metadata_pointer(authority = authority.key(), metadata_address = mint.key()),
+        mint::extensions::transfer_fee(
+            config_authority = authority.key(),
+            basis_points = 50,
+            maximum_fee = 5_000
+        ),
+        mint::extensions::transfer_hook(program_id = hook_program.key()),
+        mint::extensions::non_transferable,
+    )]
+    pub mint: Mut<SignerNoCheck<InterfaceAccount<Mint>>>,
+    #[constraint(
+        mint::token_program = token_program
+    )]
+    pub other_mint: InterfaceAccount<Mint>,
+    pub token_program: Interface<TokenProgram>,
+    pub system_program: Program<System>,
+}
+
+pub fn main() {}
@@ -0,0 +1,133 @@
+use {
+    core::mem::MaybeUninit,
+    pinocchio::{
+        account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE},
+        entrypoint::deserialize,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_token::{
+        Mint, MintCpi, Multisig, TokenAccount, TokenAccountCpi,
+    },
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct TreasuryTransfer {
+    #[constraint(
+        multisig::signers
+    )]
+    pub multisig: InterfaceAccount<Multisig>,
+    #[constraint(
+        token::owner = multisig
+    )]
+    pub treasury: Mut<InterfaceAccount<TokenAccount>>,
+    pub destination: Mut<InterfaceAccount<TokenAccount>>,
+}
+
+#[context]
+pub struct TreasuryMint {
+    #[constraint(
+        multisig::signers @ ProgramError::MissingRequiredSignature
+    )]
+    pub multisig: Account<Multisig>,
+    pub mint: Mut<InterfaceAccount<Mint>>,
+    pub destination: Mut<InterfaceAccount<TokenAccount>>,
+}
+
+pub fn transfer(ctx: TreasuryTransfer, Remaining(signers): Remaining) -> ProgramResult {
+    ctx.treasury
+        .transfer_multisig(&ctx.destination, &ctx.multisig, signers, 10, None)
+}
+
+pub fn mint(ctx: TreasuryMint, Remaining(signers): Remaining) -> ProgramResult {
+    ctx.mint
+        .mint_to_multisig(&ctx.destination, &ctx.multisig, signers, 10, None)
+}
+
+#[context]
+pub struct Approval {
+    #[constraint(
+        multisig::signers
+    )]
+    pub multisig: Account<Multisig>,
+}
+
+#[context]
+pub struct Approve {
+    #[flatten]
+    pub approval: Approval,
+    pub authority: Signer,
+}
+
+const _: () = assert!(<Approve as FlattenContext>::ACCOUNTS_LEN == 2);
+
+pub fn approve(_ctx: Approve) -> ProgramResult {
+    Ok(())
+}
+
+/// Serializes the accounts the way the runtime does, all of them are writable signers.
+fn account_infos(accounts: &[(Pubkey, &[u8])]) -> Vec<AccountInfo> {
+    let mut input = Vec::new();
+    input.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for (i, (owner, data)) in accounts.iter().enumerate() {
+        input.extend_from_slice(&[u8::MAX, 1, 1, 0, 0, 0, 0, 0]);
+        input.extend_from_slice(&[i as u8 + 1; 32]);
+        input.extend_from_slice(owner);
+        input.extend_from_slice(&1u64.to_le_bytes());
+        input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        input.extend_from_slice(data);
+        input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        input.resize(input.len().next_multiple_of(8), 0);
+        input.extend_from_slice(&u64::MAX.to_le_bytes());
+    }
+    input.extend_from_slice(&0u64.to_le_bytes());
+    input.extend_from_slice(&ID);
+
+    let buffer = Box::leak(vec![0u64; input.len().div_ceil(8)].into_boxed_slice());
+    let ptr = buffer.as_mut_ptr() as *mut u8;
+    unsafe { core::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+
+    let mut infos = [MaybeUninit::<AccountInfo>::uninit(); 8];
+    let (_, len, _) = unsafe { deserialize::<8>(ptr, &mut infos) };
+
+    infos[..len]
+        .iter()
+        .map(|info| unsafe { info.assume_init() })
+        .collect()
+}
+
+/// A 1 of 1 multisig.
+fn multisig(signer: Pubkey) -> Vec<u8> {
+    let mut data = vec![1, 1, 1];
+    data.extend_from_slice(&signer);
+    data.resize(Multisig::LEN, 0);
+    data
+}
+
+pub fn main() {
+    // The signers of a flattened context follow the top-level context, not the nested one.
+    let accounts = account_infos(&[
+        (Multisig::OWNER, &multisig([2; 32])),
+        (Pubkey::default(), &[]),
+    ]);
+    let result = Handler::call(approve, &ID, &mut accounts.as_slice(), &mut [].as_slice());
+    assert!(result.is_err());
+
+    let accounts = account_infos(&[
+        (Multisig::OWNER, &multisig([3; 32])),
+        (Pubkey::default(), &[]),
+        (Pubkey::default(), &[]),
+    ]);
+    let result = Handler::call(approve, &ID, &mut accounts.as_slice(), &mut [].as_slice());
+    assert!(result.is_ok());
+}
@@ -0,0 +1,42 @@
+use {
+    bytemuck::{AnyBitPattern, NoUninit},
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_account_macro::*,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_utility_traits::ReallocAccount,
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(NoUninit, AnyBitPattern, AccountState, Copy, Clone)]
+#[repr(C)]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[context]
+#[args(len: u64)]
+pub struct ReallocContext {
+    pub payer: Mut<Signer>,
+    #[constraint(
+        realloc = Counter::SPACE + args.len as usize,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub counter: Mut<Account<Counter>>,
+    pub system: Program<System>,
+}
+
+pub fn main() {}
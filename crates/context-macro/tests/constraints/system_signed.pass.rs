@@ -0,0 +1,43 @@
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::{Pubkey, *},
+        seeds,
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_utility_traits::SystemCpiSigned,
+};
+
+mod instruction {
+    pub use pinocchio::instruction::{Seed, Signer as CpiSigner};
+}
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct Withdraw {
+    pub owner: Mut<Signer>,
+    #[constraint(
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
+    )]
+    pub vault: Mut<SystemAccount>,
+    pub system_program: Program<System>,
+}
+
+pub fn withdraw(ctx: Withdraw) -> ProgramResult {
+    let seeds = ctx.vault_signer_seeds();
+    let signer = instruction::CpiSigner::from(&seeds);
+
+    ctx.vault.transfer_signed(&ctx.owner, 1_000, &[signer])
+}
+
+pub fn main() {}
@@ -0,0 +1,40 @@
+use {
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, rent::Rent},
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct SysvarContext {
+    pub payer: Signer,
+    pub rent: Sysvar<Rent>,
+    pub clock: Sysvar<Clock>,
+    pub instructions: Sysvar<Instructions>,
+    pub slot_hashes: Sysvar<SlotHashes>,
+    pub stake_history: Sysvar<StakeHistory>,
+}
+
+pub fn read(ctx: SysvarContext) -> ProgramResult {
+    let _ = ctx.rent.data()?.minimum_balance(0);
+    let _ = ctx.clock.data()?.slot;
+    let _ = ctx.instructions.data()?.sysvar().num_instructions();
+    let _ = ctx.slot_hashes.data()?.sysvar().len();
+    let _ = ctx.stake_history.data()?.get(0);
+
+    Ok(())
+}
+
+pub fn main() {}
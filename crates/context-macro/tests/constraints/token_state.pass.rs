@@ -0,0 +1,46 @@
+use {
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_token::{AtaTokenProgram, Mint, SplCreateToken, TokenAccount, TokenProgram},
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct CheckContext {
+    pub payer: Mut<Signer>,
+    pub authority: UncheckedAccount,
+    #[constraint(
+        mint::decimals = 6,
+        mint::authority = authority.key(),
+        mint::freeze_authority = *authority.key() @ ProgramError::InvalidArgument
+    )]
+    pub mint: InterfaceAccount<Mint>,
+    #[constraint(
+        token::mint = mint,
+        token::delegate = authority,
+        token::close_authority = payer @ ProgramError::InvalidAccountData,
+        token::is_frozen = false
+    )]
+    pub vault: InterfaceAccount<TokenAccount>,
+    #[constraint(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        token::is_frozen = false
+    )]
+    pub other: Mut<InterfaceAccount<TokenAccount>>,
+    pub ata_program: Program<AtaTokenProgram>,
+    pub token_program: Program<TokenProgram>,
+    pub system_program: Program<System>,
+}
+
+pub fn main() {}
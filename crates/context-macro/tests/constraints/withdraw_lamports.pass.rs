@@ -0,0 +1,47 @@
+use {
+    bytemuck::{AnyBitPattern, NoUninit},
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{rent::Rent, Sysvar},
+    },
+    pinocchio_pubkey::declare_id,
+    typhoon_account_macro::*,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_utility_traits::WithdrawLamports,
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(NoUninit, AnyBitPattern, AccountState, Copy, Clone)]
+#[repr(C)]
+pub struct Vault {
+    pub authority: Pubkey,
+}
+
+#[context]
+pub struct Withdraw {
+    pub authority: Mut<Signer>,
+    #[constraint(
+        has_one = authority
+    )]
+    pub vault: Mut<Account<Vault>>,
+}
+
+pub fn withdraw(ctx: Withdraw) -> ProgramResult {
+    let rent = Rent::get()?;
+    ctx.vault.withdraw(&ctx.authority, 1_000, &rent)
+}
+
+pub fn withdraw_excess(ctx: Withdraw) -> ProgramResult<u64> {
+    ctx.vault.withdraw_excess(&ctx.authority, &Rent::get()?)
+}
+
+pub fn main() {}
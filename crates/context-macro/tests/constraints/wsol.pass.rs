@@ -0,0 +1,39 @@
+use {
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    pinocchio_pubkey::declare_id,
+    typhoon_accounts::*,
+    typhoon_context::*,
+    typhoon_context_macro::*,
+    typhoon_errors::*,
+    typhoon_program_id_macro::program_id,
+    typhoon_token::{unwrap_sol, wrap_sol, TokenAccount},
+};
+
+pub type ProgramResult<T = ()> = Result<T, Error>;
+
+program_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[context]
+pub struct WrapContext {
+    pub owner: Mut<Signer>,
+    #[constraint(
+        token::owner = owner,
+        token::native
+    )]
+    pub wsol: Mut<InterfaceAccount<TokenAccount>>,
+    #[constraint(
+        token::native @ ProgramError::InvalidAccountData
+    )]
+    pub other_wsol: InterfaceAccount<TokenAccount>,
+    pub destination: Mut<SystemAccount>,
+}
+
+pub fn wrap(ctx: WrapContext) -> ProgramResult {
+    wrap_sol(&ctx.owner, &ctx.wsol, 1_000)
+}
+
+pub fn unwrap(ctx: WrapContext) -> ProgramResult {
+    unwrap_sol(&ctx.wsol, &ctx.destination, &ctx.owner, None)
+}
+
+pub fn main() {}
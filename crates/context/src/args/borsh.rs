@@ -11,6 +11,8 @@ impl<T> HandlerContext<'_, '_, '_> for BorshArg<T>
 where
     T: BorshDeserialize,
 {
+    const LAZY_ACCOUNTS_LEN: Option<usize> = Some(0);
+
     #[inline(always)]
     fn from_entrypoint(
         _program_id: &Pubkey,
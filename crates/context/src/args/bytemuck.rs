@@ -12,6 +12,8 @@ impl<'c, T> HandlerContext<'_, '_, 'c> for Arg<'c, T>
 where
     T: AnyBitPattern,
 {
+    const LAZY_ACCOUNTS_LEN: Option<usize> = Some(0);
+
     #[inline(always)]
     fn from_entrypoint(
         _program_id: &Pubkey,
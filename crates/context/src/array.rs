@@ -19,6 +19,11 @@ impl<'a, 'b, 'c, T, const N: usize> HandlerContext<'a, 'b, 'c> for Array<T, N>
 where
     T: HandlerContext<'a, 'b, 'c> + Context,
 {
+    const LAZY_ACCOUNTS_LEN: Option<usize> = match T::LAZY_ACCOUNTS_LEN {
+        Some(len) => Some(len * N),
+        None => None,
+    };
+
     #[inline(always)]
     fn from_entrypoint(
         program_id: &'a pinocchio::pubkey::Pubkey,
@@ -35,4 +40,16 @@ where
         let array = unsafe { result.map(|item| item.assume_init()) };
         Ok(Array(array))
     }
+
+    #[inline(always)]
+    fn finalize(
+        program_id: &'a pinocchio::pubkey::Pubkey,
+        accounts: &mut &'b [pinocchio::account_info::AccountInfo],
+    ) -> Result<(), typhoon_errors::Error> {
+        for _ in 0..N {
+            T::finalize(program_id, accounts)?;
+        }
+
+        Ok(())
+    }
 }
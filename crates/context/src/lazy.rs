@@ -0,0 +1,155 @@
+use {
+    crate::HandlerContext,
+    core::{
+        cell::{Cell, UnsafeCell},
+        mem::MaybeUninit,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        entrypoint::{InstructionContext, MaybeAccount},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        MAX_TX_ACCOUNTS,
+    },
+    typhoon_errors::Error,
+};
+
+/// Accounts of the lazy entrypoint of `handlers!`, read from the input on demand. The accounts
+/// already read are kept to resolve the duplicated ones, they stay borrowed for the whole
+/// instruction.
+pub struct LazyAccounts {
+    context: UnsafeCell<InstructionContext>,
+    accounts: UnsafeCell<[MaybeUninit<AccountInfo>; MAX_TX_ACCOUNTS]>,
+    len: Cell<usize>,
+}
+
+impl LazyAccounts {
+    /// Splits the input in the program id, the accounts and the instruction data. The accounts are
+    /// skipped once to reach the instruction data, no `AccountInfo` is built until they are read.
+    ///
+    /// # Safety
+    ///
+    /// `input` needs to be the input of the program serialized by the runtime.
+    #[inline(always)]
+    pub unsafe fn from_input<'a>(input: *mut u8) -> (&'a Pubkey, Self, &'a [u8]) {
+        let mut cursor = InstructionContext::new_unchecked(input);
+        for _ in 0..cursor.remaining() {
+            cursor.next_account_unchecked();
+        }
+
+        let instruction_data = cursor.instruction_data_unchecked();
+        let instruction_data =
+            core::slice::from_raw_parts(instruction_data.as_ptr(), instruction_data.len());
+        let program_id = &*(cursor.program_id_unchecked() as *const Pubkey);
+
+        let accounts = LazyAccounts {
+            context: UnsafeCell::new(InstructionContext::new_unchecked(input)),
+            accounts: UnsafeCell::new([const { MaybeUninit::uninit() }; MAX_TX_ACCOUNTS]),
+            len: Cell::new(0),
+        };
+
+        (program_id, accounts, instruction_data)
+    }
+
+    /// Number of accounts not read yet, the ones past `MAX_TX_ACCOUNTS` are ignored like in the
+    /// regular entrypoint.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        // SAFETY: the context is only borrowed within the methods.
+        let remaining = unsafe { (*self.context.get()).remaining() } as usize;
+        remaining.min(MAX_TX_ACCOUNTS - self.len.get())
+    }
+
+    /// Reads the next account, `None` once all the accounts are read.
+    #[inline(always)]
+    pub fn next_account(&self) -> Option<&AccountInfo> {
+        if self.remaining() == 0 {
+            return None;
+        }
+
+        let len = self.len.get();
+        let accounts = self.accounts.get() as *mut AccountInfo;
+        // SAFETY: the context is only borrowed within the methods.
+        let account = match unsafe { (*self.context.get()).next_account() }.ok()? {
+            MaybeAccount::Account(account) => account,
+            MaybeAccount::Duplicated(index) if (index as usize) < len => {
+                // SAFETY: the accounts before `len` have been initialized.
+                unsafe { *accounts.add(index as usize) }
+            }
+            MaybeAccount::Duplicated(_) => return None,
+        };
+
+        // SAFETY: `len < MAX_TX_ACCOUNTS` and the account at `len` is not borrowed yet.
+        unsafe {
+            let slot = accounts.add(len);
+            slot.write(account);
+            self.len.set(len + 1);
+
+            Some(&*slot)
+        }
+    }
+
+    /// Reads the next `len` accounts, all the remaining ones when `None`.
+    #[inline(always)]
+    pub fn read(&self, len: Option<usize>) -> Result<&[AccountInfo], ProgramError> {
+        let start = self.len.get();
+        let len = len.unwrap_or_else(|| self.remaining());
+        for _ in 0..len {
+            self.next_account()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        }
+
+        // SAFETY: the accounts from `start` to `start + len` have been initialized.
+        Ok(unsafe {
+            core::slice::from_raw_parts((self.accounts.get() as *const AccountInfo).add(start), len)
+        })
+    }
+}
+
+/// Iterator over the accounts following the contexts of the handler. With the lazy entrypoint
+/// they are read one at a time, only when iterated.
+pub struct LazyRemaining<'a> {
+    lazy: Option<&'a LazyAccounts>,
+    accounts: core::slice::Iter<'a, AccountInfo>,
+}
+
+impl<'a> Iterator for LazyRemaining<'a> {
+    type Item = &'a AccountInfo;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.lazy {
+            Some(lazy) => lazy.next_account(),
+            None => self.accounts.next(),
+        }
+    }
+}
+
+impl<'b> HandlerContext<'_, 'b, '_> for LazyRemaining<'b> {
+    const LAZY_ACCOUNTS_LEN: Option<usize> = Some(0);
+
+    #[inline(always)]
+    fn from_entrypoint(
+        _program_id: &Pubkey,
+        accounts: &mut &'b [AccountInfo],
+        _instruction_data: &mut &[u8],
+    ) -> Result<Self, Error> {
+        Ok(LazyRemaining {
+            lazy: None,
+            accounts: accounts.iter(),
+        })
+    }
+
+    #[inline(always)]
+    fn from_lazy(
+        _program_id: &Pubkey,
+        lazy: &'b LazyAccounts,
+        _accounts: &'b [AccountInfo],
+        _instruction_data: &mut &[u8],
+    ) -> Result<Self, Error> {
+        Ok(LazyRemaining {
+            lazy: Some(lazy),
+            accounts: [].iter(),
+        })
+    }
+}
@@ -4,10 +4,11 @@ use {bytemuck::NoUninit, paste::paste};
 
 mod args;
 mod array;
+mod lazy;
 mod program_id;
 mod remaining_accounts;
 
-pub use {args::*, array::*, program_id::*, remaining_accounts::*};
+pub use {args::*, array::*, lazy::*, program_id::*, remaining_accounts::*};
 use {
     pinocchio::{account_info::AccountInfo, cpi::set_return_data, pubkey::Pubkey},
     typhoon_errors::Error,
@@ -17,11 +18,63 @@ use {
 pub trait Context {}
 
 pub trait HandlerContext<'a, 'b, 'c>: Sized {
+    /// Number of accounts read by the lazy entrypoint before calling
+    /// [`HandlerContext::from_lazy`], all the remaining ones when `None`.
+    const LAZY_ACCOUNTS_LEN: Option<usize> = None;
+
     fn from_entrypoint(
         program_id: &'a Pubkey,
         accounts: &mut &'b [AccountInfo],
         instruction_data: &mut &'c [u8],
     ) -> Result<Self, Error>;
+
+    /// Same as [`HandlerContext::from_entrypoint`] for the lazy entrypoint, `accounts` are the
+    /// [`HandlerContext::LAZY_ACCOUNTS_LEN`] accounts read for the context.
+    #[inline(always)]
+    fn from_lazy(
+        program_id: &'a Pubkey,
+        _lazy: &'b LazyAccounts,
+        mut accounts: &'b [AccountInfo],
+        instruction_data: &mut &'c [u8],
+    ) -> Result<Self, Error> {
+        Self::from_entrypoint(program_id, &mut accounts, instruction_data)
+    }
+
+    /// Called once the handler returned successfully. `accounts` starts at the first account
+    /// consumed by [`HandlerContext::from_entrypoint`] and should be advanced the same way.
+    #[inline(always)]
+    fn finalize(_program_id: &'a Pubkey, _accounts: &mut &'b [AccountInfo]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Implemented by `#[context]` structs, allows them to be flattened in another context with
+/// `#[flatten]`. The outer constraints can refer to the accounts of a flattened context by name,
+/// and its bumps and arguments are merged in the `bumps` and `args` of the outer context under
+/// the name of the field. The arguments follow the ones of the outer context.
+pub trait FlattenContext<'a, 'b, 'c>: HandlerContext<'a, 'b, 'c> {
+    /// Number of accounts consumed, the ones of the flattened contexts included.
+    const ACCOUNTS_LEN: usize;
+
+    /// Bumps of the context, `()` when it has none.
+    type Bumps;
+
+    /// Arguments of the context, `()` when it has none.
+    type Args;
+
+    /// Bumps found while validating the accounts.
+    fn bumps(&self) -> Self::Bumps;
+
+    /// Same as [`HandlerContext::from_entrypoint`], `remaining` being the accounts following the
+    /// top-level context. `args` are the arguments already read by the outer context, they are
+    /// read from `instruction_data` otherwise.
+    fn from_flattened(
+        program_id: &'a Pubkey,
+        accounts: &mut &'b [AccountInfo],
+        remaining: &'b [AccountInfo],
+        args: Option<&'c Self::Args>,
+        instruction_data: &mut &'c [u8],
+    ) -> Result<Self, Error>;
 }
 
 pub trait Handler<'a, 'b, 'c, T> {
@@ -33,6 +86,15 @@ pub trait Handler<'a, 'b, 'c, T> {
         accounts: &mut &'b [AccountInfo],
         instruction_data: &mut &'c [u8],
     ) -> Result<Self::Output, Error>;
+
+    /// Same as [`Handler::call`] for the lazy entrypoint, the accounts of each context are read
+    /// right before it.
+    fn call_lazy(
+        self,
+        program_id: &'a Pubkey,
+        accounts: &'b LazyAccounts,
+        instruction_data: &mut &'c [u8],
+    ) -> Result<Self::Output, Error>;
 }
 
 impl<F, O> Handler<'_, '_, '_, ()> for F
@@ -50,6 +112,15 @@ where
     ) -> Result<Self::Output, Error> {
         (self)()
     }
+
+    fn call_lazy(
+        self,
+        _program_id: &Pubkey,
+        _accounts: &LazyAccounts,
+        _instruction_data: &mut &[u8],
+    ) -> Result<Self::Output, Error> {
+        (self)()
+    }
 }
 
 macro_rules! impl_handler {
@@ -72,9 +143,33 @@ macro_rules! impl_handler {
             ) -> Result<Self::Output, Error> {
                 paste! {
                     $(
+                        let mut [<$t:lower _accounts>] = *accounts;
                         let [<$t:lower>] = $t::from_entrypoint(program_id, accounts, instruction_data)?;
                     )*
-                    (self)($( [<$t:lower>], )*)
+                    let output = (self)($( [<$t:lower>], )*)?;
+                    $(
+                        $t::finalize(program_id, &mut [<$t:lower _accounts>])?;
+                    )*
+                    Ok(output)
+                }
+            }
+
+            fn call_lazy(
+                self,
+                program_id: &'a Pubkey,
+                accounts: &'b LazyAccounts,
+                instruction_data: &mut &'c [u8],
+            ) -> Result<Self::Output, Error> {
+                paste! {
+                    $(
+                        let mut [<$t:lower _accounts>] = accounts.read($t::LAZY_ACCOUNTS_LEN)?;
+                        let [<$t:lower>] = $t::from_lazy(program_id, accounts, [<$t:lower _accounts>], instruction_data)?;
+                    )*
+                    let output = (self)($( [<$t:lower>], )*)?;
+                    $(
+                        $t::finalize(program_id, &mut [<$t:lower _accounts>])?;
+                    )*
+                    Ok(output)
                 }
             }
         }
@@ -98,14 +193,58 @@ pub fn handle<'a, 'b, 'c, T, H>(
 where
     H: Handler<'a, 'b, 'c, T>,
 {
-    match handler.call(program_id, &mut accounts, &mut instruction_data) {
-        Ok(res) => {
-            if core::mem::size_of::<H::Output>() > 0 {
-                set_return_data(bytemuck::bytes_of(&res));
-            }
+    handler
+        .call(program_id, &mut accounts, &mut instruction_data)
+        .map(|output| return_output(&output))
+}
 
-            Ok(())
+/// Same as [`handle`] for the lazy entrypoint of `handlers!`, the accounts are read on demand.
+pub fn handle_lazy<'a, 'b, 'c, T, H>(
+    program_id: &'a Pubkey,
+    accounts: &'b LazyAccounts,
+    mut instruction_data: &'c [u8],
+    handler: H,
+) -> Result<(), Error>
+where
+    H: Handler<'a, 'b, 'c, T>,
+{
+    handler
+        .call_lazy(program_id, accounts, &mut instruction_data)
+        .map(|output| return_output(&output))
+}
+
+#[inline(always)]
+fn return_output<O: NoUninit>(output: &O) {
+    if core::mem::size_of::<O>() > 0 {
+        set_return_data(bytemuck::bytes_of(output));
+    }
+}
+
+/// Whether one of the discriminators is a prefix of the other, used by `handlers!` to check the
+/// discriminators that are not literals at compile time.
+pub const fn discriminators_overlap(a: &[u8], b: &[u8]) -> bool {
+    let len = if a.len() < b.len() { a.len() } else { b.len() };
+    let mut i = 0;
+    while i < len {
+        if a[i] != b[i] {
+            return false;
         }
-        Err(err) => Err(err),
+        i += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discriminators_overlap() {
+        assert!(discriminators_overlap(&[1, 2], &[1, 2]));
+        assert!(discriminators_overlap(&[1], &[1, 2]));
+        assert!(discriminators_overlap(&[1, 2, 3], &[1, 2]));
+        assert!(!discriminators_overlap(&[1, 2], &[1, 3]));
+        assert!(!discriminators_overlap(&[2], &[1, 2]));
     }
 }
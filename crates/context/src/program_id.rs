@@ -6,6 +6,8 @@ use {
 pub struct ProgramIdArg<'a>(pub &'a Pubkey);
 
 impl<'a> HandlerContext<'a, '_, '_> for ProgramIdArg<'a> {
+    const LAZY_ACCOUNTS_LEN: Option<usize> = Some(0);
+
     #[inline(always)]
     fn from_entrypoint(
         program_id: &'a Pubkey,
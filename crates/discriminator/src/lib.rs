@@ -2,8 +2,13 @@
 
 use sha2::{Digest, Sha256};
 
+/// Namespace of the event discriminators, an event and an account can share a name.
+pub const EVENT_NAMESPACE: &str = "event";
+
 pub struct DiscriminatorBuilder<'a> {
     pub name: &'a str,
+    /// Hashed as `<namespace>:<name>` when set, to keep the kinds of items apart.
+    pub namespace: Option<&'a str>,
     pub layout_version: u8,
 }
 
@@ -11,10 +16,16 @@ impl<'a> DiscriminatorBuilder<'a> {
     pub fn new(name: &'a str) -> Self {
         DiscriminatorBuilder {
             name,
+            namespace: None,
             layout_version: 1,
         }
     }
 
+    pub fn namespace(mut self, namespace: &'a str) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
     pub fn layout(mut self, version: u8) -> Self {
         self.layout_version = version;
         self
@@ -22,6 +33,10 @@ impl<'a> DiscriminatorBuilder<'a> {
 
     pub fn build(self) -> [u8; 8] {
         let mut hasher = Sha256::new();
+        if let Some(namespace) = self.namespace {
+            hasher.update(namespace);
+            hasher.update(":");
+        }
         hasher.update(self.name);
         let hash = hasher.finalize();
 
@@ -33,6 +48,21 @@ impl<'a> DiscriminatorBuilder<'a> {
     }
 }
 
+/// Anchor discriminator: `sha256("<namespace>:<name>")[..8]`, instructions use the `global`
+/// namespace.
+pub fn sighash(namespace: &str, name: &str) -> [u8; 8] {
+    let mut hasher = Sha256::new();
+    hasher.update(namespace);
+    hasher.update(":");
+    hasher.update(name);
+    let hash = hasher.finalize();
+
+    let mut discriminator = [0; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+
+    discriminator
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,5 +78,19 @@ mod tests {
         let expected = [75, 166, 151, 53, 2, 0, 0, 0];
 
         assert_eq!(discriminator, expected);
+
+        let discriminator = DiscriminatorBuilder::new("state")
+            .namespace("event")
+            .build();
+        assert_ne!(discriminator, DiscriminatorBuilder::new("state").build());
+        assert_eq!(discriminator[..4], sighash("event", "state")[..4]);
+    }
+
+    #[test]
+    fn sighash_test() {
+        assert_eq!(
+            sighash("global", "initialize"),
+            [175, 175, 109, 31, 13, 152, 155, 237]
+        );
     }
 }
@@ -13,6 +13,23 @@ pub enum ErrorCode {
     TokenConstraintViolated,
     BufferFull,
     InvalidReturnData,
+    ConstraintViolated,
+    AddressConstraint,
+    OwnerConstraint,
+    InvalidEventAuthority,
+    UnexpectedInstruction,
+    InvalidSignatureInstruction,
+    SignatureNotVerified,
+    ForbiddenTokenExtension,
+    InvalidExtraAccount,
+    TokenDelegateConstraint,
+    TokenCloseAuthorityConstraint,
+    TokenFrozenConstraint,
+    MintDecimalsConstraint,
+    MintAuthorityConstraint,
+    MintFreezeAuthorityConstraint,
+    TokenNativeConstraint,
+    MultisigSignersConstraint,
 }
 
 impl TryFrom<u32> for ErrorCode {
@@ -32,6 +49,23 @@ impl TryFrom<u32> for ErrorCode {
             108 => Ok(ErrorCode::TokenConstraintViolated),
             109 => Ok(ErrorCode::BufferFull),
             110 => Ok(ErrorCode::InvalidReturnData),
+            111 => Ok(ErrorCode::ConstraintViolated),
+            112 => Ok(ErrorCode::AddressConstraint),
+            113 => Ok(ErrorCode::OwnerConstraint),
+            114 => Ok(ErrorCode::InvalidEventAuthority),
+            115 => Ok(ErrorCode::UnexpectedInstruction),
+            116 => Ok(ErrorCode::InvalidSignatureInstruction),
+            117 => Ok(ErrorCode::SignatureNotVerified),
+            118 => Ok(ErrorCode::ForbiddenTokenExtension),
+            119 => Ok(ErrorCode::InvalidExtraAccount),
+            120 => Ok(ErrorCode::TokenDelegateConstraint),
+            121 => Ok(ErrorCode::TokenCloseAuthorityConstraint),
+            122 => Ok(ErrorCode::TokenFrozenConstraint),
+            123 => Ok(ErrorCode::MintDecimalsConstraint),
+            124 => Ok(ErrorCode::MintAuthorityConstraint),
+            125 => Ok(ErrorCode::MintFreezeAuthorityConstraint),
+            126 => Ok(ErrorCode::TokenNativeConstraint),
+            127 => Ok(ErrorCode::MultisigSignersConstraint),
             _ => Err(ProgramError::InvalidArgument),
         }
     }
@@ -67,6 +101,31 @@ impl ToStr for ErrorCode {
             ErrorCode::TokenConstraintViolated => "Error: Token constraint was violated",
             ErrorCode::BufferFull => "Error: Buffer is full",
             ErrorCode::InvalidReturnData => "Error: The return data is invalid",
+            ErrorCode::ConstraintViolated => "Error: A constraint was violated",
+            ErrorCode::AddressConstraint => "Error: address constraint violated",
+            ErrorCode::OwnerConstraint => "Error: owner constraint violated",
+            ErrorCode::InvalidEventAuthority => "Error: Invalid event authority",
+            ErrorCode::UnexpectedInstruction => "Error: Unexpected instruction in the transaction",
+            ErrorCode::InvalidSignatureInstruction => {
+                "Error: Invalid signature verification instruction"
+            }
+            ErrorCode::SignatureNotVerified => "Error: Expected signature was not verified",
+            ErrorCode::ForbiddenTokenExtension => "Error: Token account has a forbidden extension",
+            ErrorCode::InvalidExtraAccount => {
+                "Error: Extra account does not match the transfer hook account list"
+            }
+            ErrorCode::TokenDelegateConstraint => "Error: token delegate constraint violated",
+            ErrorCode::TokenCloseAuthorityConstraint => {
+                "Error: token close_authority constraint violated"
+            }
+            ErrorCode::TokenFrozenConstraint => "Error: token is_frozen constraint violated",
+            ErrorCode::MintDecimalsConstraint => "Error: mint decimals constraint violated",
+            ErrorCode::MintAuthorityConstraint => "Error: mint authority constraint violated",
+            ErrorCode::MintFreezeAuthorityConstraint => {
+                "Error: mint freeze_authority constraint violated"
+            }
+            ErrorCode::TokenNativeConstraint => "Error: token native constraint violated",
+            ErrorCode::MultisigSignersConstraint => "Error: multisig signers constraint violated",
         }
     }
 }
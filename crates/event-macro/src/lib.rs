@@ -0,0 +1,33 @@
+use {
+    quote::{quote, ToTokens},
+    syn::{parse_macro_input, spanned::Spanned, Error, Item},
+    typhoon_discriminator::{DiscriminatorBuilder, EVENT_NAMESPACE},
+};
+
+#[proc_macro_derive(Event)]
+pub fn derive_event(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(item as Item);
+    let (name, generics) = match item {
+        Item::Struct(ref item_struct) => (&item_struct.ident, &item_struct.generics),
+        _ => {
+            return Error::new(item.span(), "Invalid event type")
+                .into_compile_error()
+                .into()
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let discriminator = DiscriminatorBuilder::new(&name.to_string())
+        .namespace(EVENT_NAMESPACE)
+        .build();
+
+    quote! {
+        impl #impl_generics Discriminator for #name #ty_generics #where_clause {
+            const DISCRIMINATOR: &'static [u8] = &[#(#discriminator),*];
+        }
+
+        impl #impl_generics Event for #name #ty_generics #where_clause {}
+    }
+    .into_token_stream()
+    .into()
+}
@@ -1,52 +1,199 @@
 use {
+    heck::ToUpperCamelCase,
     proc_macro::TokenStream,
-    quote::{quote, ToTokens},
-    syn::{parse::Parse, parse_macro_input, punctuated::Punctuated, Path, Token},
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::{format_ident, quote, quote_spanned, ToTokens},
+    syn::{parse_macro_input, spanned::Spanned},
+    typhoon_syn::{DiscriminatorMode, HandlerDiscriminator, HandlerEntry, Handlers},
 };
 
 #[proc_macro]
 pub fn handlers(item: TokenStream) -> TokenStream {
-    parse_macro_input!(item as Handlers)
-        .to_token_stream()
+    let handlers = parse_macro_input!(item as Handlers);
+
+    generate(&handlers)
+        .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
 
-struct Handlers {
-    instructions: Punctuated<Path, Token![,]>,
-}
+fn generate(handlers: &Handlers) -> syn::Result<TokenStream2> {
+    let discriminators = handlers.discriminators()?;
+    let discriminator_len = handlers.mode.discriminator_len();
 
-impl Parse for Handlers {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let instructions = Punctuated::<Path, Token![,]>::parse_terminated(input)?;
+    let mut explicit = Vec::new();
+    let mut instructions = Vec::new();
+    let mut markers = Vec::new();
+    let mut assertions = Vec::new();
+    let handle = if handlers.lazy {
+        quote!(handle_lazy)
+    } else {
+        quote!(handle)
+    };
 
-        Ok(Handlers { instructions })
-    }
-}
+    for (i, (entry, discriminator)) in handlers.entries.iter().zip(&discriminators).enumerate() {
+        let path = &entry.path;
+        let bytes = discriminator_bytes(discriminator);
+
+        if let HandlerDiscriminator::Expr(expr) = discriminator {
+            let message = format!(
+                "The discriminator of `{}` cannot be empty.",
+                path.to_token_stream()
+            );
+            assertions.push(quote_spanned! {expr.span()=>
+                const _: () = assert!(!<[u8]>::is_empty(#bytes), #message);
+            });
+        }
+
+        // The event CPI tag is matched before the instructions.
+        if handlers.event_cpi {
+            let message = format!(
+                "The discriminator of `{}` overlaps with the event CPI tag.",
+                path.to_token_stream()
+            );
+            assertions.push(quote_spanned! {discriminator_span(entry)=>
+                const _: () = assert!(!discriminators_overlap(#bytes, &EVENT_IX_TAG), #message);
+            });
+        }
 
-impl ToTokens for Handlers {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let instructions = self.instructions.iter().enumerate().map(|(i, val)| {
-            let i = i as u8;
-            quote! {
-                #i => handle(program_id, accounts, data, #val),
+        // Literal discriminators are already checked by the parser.
+        for (other_entry, other) in handlers.entries.iter().zip(&discriminators).take(i) {
+            if let (HandlerDiscriminator::Bytes(_), HandlerDiscriminator::Bytes(_)) =
+                (discriminator, other)
+            {
+                continue;
             }
-        });
 
-        let expanded = quote! {
-            program_entrypoint!(process_instruction);
+            let other_bytes = discriminator_bytes(other);
+            let message = format!(
+                "The discriminator of `{}` overlaps with the one of `{}`.",
+                path.to_token_stream(),
+                other_entry.path.to_token_stream()
+            );
+            assertions.push(quote_spanned! {discriminator_span(entry)=>
+                const _: () = assert!(!discriminators_overlap(#bytes, #other_bytes), #message);
+            });
+        }
 
-            #[inline(always)]
-            pub fn process_instruction(
-                program_id: &Pubkey,
-                accounts: &[AccountInfo],
-                instruction_data: &[u8],
-            ) -> Result<(), ProgramError> {
-                let (discriminator, data) = instruction_data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
-                let result = match discriminator {
-                    #(#instructions)*
-                    _ => Err(ProgramError::InvalidInstructionData.into()),
+        match discriminator {
+            HandlerDiscriminator::Bytes(discriminator)
+                if discriminator.len() == discriminator_len =>
+            {
+                let pattern = match handlers.mode {
+                    DiscriminatorMode::Index => {
+                        let discriminator = discriminator[0];
+                        quote!(#discriminator)
+                    }
+                    DiscriminatorMode::U16 | DiscriminatorMode::Anchor => {
+                        quote!([#(#discriminator),*])
+                    }
                 };
 
+                instructions.push(quote! {
+                    #pattern => dispatch(#handle(program_id, accounts, data, #path)),
+                });
+            }
+            _ => explicit.push(quote! {
+                if let Some(data) = instruction_data.strip_prefix(#bytes) {
+                    return dispatch(#handle(program_id, accounts, data, #path));
+                }
+            }),
+        }
+
+        if !handlers.markers {
+            continue;
+        }
+
+        let name = format_ident!("{}", entry.name()?.to_string().to_upper_camel_case());
+        markers.push(quote! {
+            pub struct #name;
+
+            impl ProgramId for #name {
+                const ID: Pubkey = crate::ID;
+            }
+
+            impl Discriminator for #name {
+                const DISCRIMINATOR: &'static [u8] = #bytes;
+            }
+        });
+    }
+
+    // The event CPI and the fallback work on all the accounts.
+    let all_accounts = if handlers.lazy {
+        quote!(accounts.read(None)?)
+    } else {
+        quote!(accounts)
+    };
+
+    let event_cpi = handlers.event_cpi.then(|| {
+        quote! {
+            if instruction_data.starts_with(&EVENT_IX_TAG) {
+                return handle_event_cpi(program_id, #all_accounts, instruction_data).map_err(Into::into);
+            }
+        }
+    });
+
+    let split = match handlers.mode {
+        DiscriminatorMode::Index => quote!(split_first()),
+        DiscriminatorMode::U16 | DiscriminatorMode::Anchor => {
+            quote!(split_first_chunk::<#discriminator_len>())
+        }
+    };
+
+    let fallback = match &handlers.fallback {
+        Some(fallback) => {
+            quote!(dispatch(#fallback(program_id, #all_accounts, instruction_data)))
+        }
+        None => quote!(dispatch(Err(ProgramError::InvalidInstructionData.into()))),
+    };
+
+    let markers = handlers.markers.then(|| {
+        quote! {
+            /// Marker types of the instructions, used to introspect the transaction.
+            pub mod discriminators {
+                use super::*;
+
+                #(#markers)*
+            }
+        }
+    });
+
+    let (entrypoint, accounts_ty) = if handlers.lazy {
+        let entrypoint = quote! {
+            /// Program entrypoint, the accounts are read on demand.
+            #[no_mangle]
+            pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+                let (program_id, accounts, instruction_data) = LazyAccounts::from_input(input);
+
+                match process_instruction(program_id, &accounts, instruction_data) {
+                    Ok(()) => pinocchio::SUCCESS,
+                    Err(error) => error.into(),
+                }
+            }
+        };
+
+        (entrypoint, quote!(&LazyAccounts))
+    } else {
+        (
+            quote!(program_entrypoint!(process_instruction);),
+            quote!(&[AccountInfo]),
+        )
+    };
+
+    Ok(quote! {
+        #(#assertions)*
+
+        #entrypoint
+
+        #[inline(always)]
+        pub fn process_instruction(
+            program_id: &Pubkey,
+            accounts: #accounts_ty,
+            instruction_data: &[u8],
+        ) -> Result<(), ProgramError> {
+            #event_cpi
+
+            #[inline(always)]
+            fn dispatch(result: ProgramResult) -> Result<(), ProgramError> {
                 #[cfg(feature = "logging")]
                 result.inspect_err(log_error)?;
 
@@ -55,8 +202,32 @@ impl ToTokens for Handlers {
 
                 Ok(())
             }
-        };
 
-        expanded.to_tokens(tokens);
+            #(#explicit)*
+
+            let Some((discriminator, data)) = instruction_data.#split else {
+                return #fallback;
+            };
+            match discriminator {
+                #(#instructions)*
+                _ => #fallback,
+            }
+        }
+
+        #markers
+    })
+}
+
+fn discriminator_span(entry: &HandlerEntry) -> Span {
+    match &entry.discriminator {
+        Some(expr) => expr.span(),
+        None => entry.path.span(),
+    }
+}
+
+fn discriminator_bytes(discriminator: &HandlerDiscriminator) -> TokenStream2 {
+    match discriminator {
+        HandlerDiscriminator::Bytes(bytes) => quote!(&[#(#bytes),*]),
+        HandlerDiscriminator::Expr(expr) => quote!(&#expr),
     }
 }
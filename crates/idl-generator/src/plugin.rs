@@ -1,19 +1,21 @@
 use {
     crate::visitors::{
         get_type_node, ApplyInstructionVisitor, ProgramVisitor, SetAccountVisitor,
-        SetDefinedTypesVisitor, SetErrorsVisitor, SetProgramIdVisitor,
+        SetDefinedTypesVisitor, SetErrorsVisitor, SetEventVisitor, SetProgramIdVisitor,
     },
+    base64::{prelude::BASE64_STANDARD, Engine},
     codama::{
-        CamelCaseString, CodamaResult, CombineModulesVisitor, ComposeVisitor,
+        BytesEncoding, CamelCaseString, CodamaResult, CombineModulesVisitor, ComposeVisitor,
         ConstantDiscriminatorNode, ConstantValueNode, DefinedTypeLinkNode, DiscriminatorNode, Docs,
         InstructionAccountNode, InstructionArgumentNode, InstructionNode,
         InstructionOptionalAccountStrategy, IsAccountSigner, NumberFormat::U8, NumberTypeNode,
-        NumberValueNode, SetProgramMetadataVisitor, StructFieldTypeNode, StructTypeNode, TypeNode,
+        NumberValueNode, ProgramIdValueNode, PublicKeyValueNode, SetProgramMetadataVisitor,
+        StructFieldTypeNode, StructTypeNode, TypeNode,
     },
     codama_korok_plugins::KorokPlugin,
     codama_korok_visitors::KorokVisitable,
     hashbrown::HashMap,
-    syn::{Error, Type},
+    syn::{Error, Ident, Type},
     typhoon_syn::{Arguments, InstructionArg},
 };
 
@@ -35,6 +37,7 @@ impl KorokPlugin for TyphoonPlugin {
         let mut default_visitor = ComposeVisitor::new()
             .with(ApplyInstructionVisitor::new(ixs))
             .with(SetAccountVisitor::new())
+            .with(SetEventVisitor::new())
             .with(SetDefinedTypesVisitor::new())
             .with(SetErrorsVisitor::new(program_visitor.errors_name))
             .with(SetProgramIdVisitor::new())
@@ -70,9 +73,17 @@ fn resolve_instructions(
                         .get(&ctx.to_string())
                         .ok_or(syn::Error::new_spanned(ctx, ""))?;
 
-                    for account in &context.accounts {
+                    let resolve = |name: &Ident| program.contexts.get(&name.to_string());
+
+                    for account in context.flatten_accounts(&resolve)? {
                         accounts.push(InstructionAccountNode {
-                            default_value: None,
+                            default_value: if account.is_program_address() {
+                                Some(ProgramIdValueNode::new().into())
+                            } else {
+                                account
+                                    .fixed_address()
+                                    .map(|address| PublicKeyValueNode::new(address).into())
+                            },
                             docs: Docs::from(account.docs.clone()),
                             is_optional: account.meta.is_optional,
                             is_signer: if account.meta.is_optional && account.meta.is_signer {
@@ -85,9 +96,9 @@ fn resolve_instructions(
                         });
                     }
 
-                    if let Some(args) = &context.arguments {
+                    for (context_name, args) in context.flatten_arguments(&resolve)? {
                         arguments.push(InstructionArgumentNode {
-                            name: CamelCaseString::new(format!("{}_args", context.name)),
+                            name: CamelCaseString::new(format!("{context_name}_args")),
                             r#type: match args {
                                 Arguments::Values(arguments) => TypeNode::Struct(StructTypeNode {
                                     fields: arguments
@@ -129,10 +140,16 @@ fn resolve_instructions(
             name,
             InstructionNode {
                 discriminators: vec![DiscriminatorNode::Constant(ConstantDiscriminatorNode::new(
-                    ConstantValueNode::new(
-                        NumberTypeNode::le(U8),
-                        NumberValueNode::new(*dis as u8),
-                    ),
+                    match dis.as_slice() {
+                        [dis] => ConstantValueNode::new(
+                            NumberTypeNode::le(U8),
+                            NumberValueNode::new(*dis),
+                        ),
+                        dis => ConstantValueNode::bytes(
+                            BytesEncoding::Base64,
+                            BASE64_STANDARD.encode(dis),
+                        ),
+                    },
                     0,
                 ))],
                 accounts,
@@ -3,10 +3,11 @@ mod program_visitor;
 mod set_account_visitor;
 mod set_defined_types;
 mod set_errors_visitor;
+mod set_event_visitor;
 mod set_program_id_visitor;
 mod set_type_visitor;
 
 pub use {
     apply_instruction_visitor::*, program_visitor::*, set_account_visitor::*, set_defined_types::*,
-    set_errors_visitor::*, set_program_id_visitor::*, set_type_visitor::*,
+    set_errors_visitor::*, set_event_visitor::*, set_program_id_visitor::*, set_type_visitor::*,
 };
@@ -0,0 +1,69 @@
+use {
+    crate::visitors::SetBorshTypesVisitor,
+    base64::{prelude::BASE64_STANDARD, Engine},
+    codama::{
+        CamelCaseString, CombineTypesVisitor, ComposeVisitor, ConstantValueNode, DefinedTypeNode,
+        Docs, HiddenPrefixTypeNode, KorokVisitor, Node, SetLinkTypesVisitor, TypeNode,
+    },
+    typhoon_discriminator::{DiscriminatorBuilder, EVENT_NAMESPACE},
+    typhoon_syn::Docs as TyphoonDocs,
+};
+
+pub struct SetEventVisitor {
+    visitor: Box<dyn KorokVisitor>,
+}
+
+impl Default for SetEventVisitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetEventVisitor {
+    pub fn new() -> Self {
+        Self {
+            visitor: Box::new(
+                ComposeVisitor::new()
+                    .with(SetBorshTypesVisitor::new())
+                    .with(SetLinkTypesVisitor::new())
+                    .with(CombineTypesVisitor::new()),
+            ),
+        }
+    }
+}
+
+impl KorokVisitor for SetEventVisitor {
+    fn visit_struct(&mut self, korok: &mut codama_koroks::StructKorok) -> codama::CodamaResult<()> {
+        if !korok.attributes.has_derive(&[""], "Event") {
+            return Ok(());
+        };
+
+        self.visitor.visit_struct(korok)?;
+
+        let Some(Node::DefinedType(DefinedTypeNode {
+            r#type: ty @ TypeNode::Struct(_),
+            ..
+        })) = korok.node.take()
+        else {
+            return Ok(());
+        };
+
+        let dis = DiscriminatorBuilder::new(&korok.ast.ident.to_string())
+            .namespace(EVENT_NAMESPACE)
+            .build();
+
+        korok.node = Some(Node::DefinedType(DefinedTypeNode {
+            name: CamelCaseString::new(korok.ast.ident.to_string()),
+            docs: Docs::from(TyphoonDocs::from(korok.ast.attrs.as_slice()).into_vec()),
+            r#type: TypeNode::HiddenPrefix(HiddenPrefixTypeNode {
+                r#type: Box::new(ty),
+                prefix: vec![ConstantValueNode::bytes(
+                    codama::BytesEncoding::Base64,
+                    BASE64_STANDARD.encode(dis),
+                )],
+            }),
+        }));
+
+        Ok(())
+    }
+}
@@ -24,7 +24,13 @@ fn generate_ctx(ctxs: &hashbrown::HashMap<String, Context>) -> TokenStream {
                 generate_arg((&format_ident!("args"), &parse_quote!(#arg_ty)))
             })
             .unzip();
-        let (acc_fields, acc_assigns) = generate_accounts(&ctx.accounts);
+        let (mut acc_fields, mut acc_assigns) = generate_accounts(&ctx.accounts);
+        for nested in ctx.nested.iter().rev() {
+            let name = &nested.name;
+            let nested_ctx = format_ident!("{}Context", nested.context);
+            acc_fields.insert(nested.index, quote!(pub #name: #nested_ctx,));
+            acc_assigns.insert(nested.index, quote!(self.#name.append(data, accounts);));
+        }
 
         quote! {
             pub struct #ctx_name {
@@ -34,8 +40,8 @@ fn generate_ctx(ctxs: &hashbrown::HashMap<String, Context>) -> TokenStream {
 
             impl #ctx_name {
                 fn append(&self, data: &mut std::vec::Vec<u8>, accounts: &mut std::vec::Vec<::solana_instruction::AccountMeta>) {
-                    #(#acc_assigns)*
                     #args_assign
+                    #(#acc_assigns)*
                 }
             }
         }
@@ -56,6 +62,13 @@ fn generate_accounts(accounts: &[InstructionAccount]) -> (Vec<TokenStream>, Vec<
         .iter()
         .map(|acc| {
             let name = &acc.name;
+            if acc.is_program_address() {
+                return (
+                    TokenStream::new(),
+                    quote!(accounts.push(::solana_instruction::AccountMeta::new_readonly(crate::ID.into(), false));),
+                );
+            }
+
             let is_signer = acc.meta.is_signer;
             let field = if acc.meta.is_optional {
                 quote!(pub #name: Option<::solana_pubkey::Pubkey>,)
@@ -89,7 +102,7 @@ fn generate_accounts(accounts: &[InstructionAccount]) -> (Vec<TokenStream>, Vec<
 
 impl Generator for ClientGenerator {
     fn generate_token(
-        instructions: &hashbrown::HashMap<usize, Instruction>,
+        instructions: &hashbrown::HashMap<Vec<u8>, Instruction>,
         context: &hashbrown::HashMap<String, Context>,
         extra_token: TokenStream,
     ) -> TokenStream {
@@ -98,6 +111,8 @@ impl Generator for ClientGenerator {
         token.extend(generate_ctx(context));
         token.extend(extra_token);
 
+        let resolve = |name: &Ident| context.get(&name.to_string());
+
         instructions.iter().for_each(|(discriminator, ix)| {
             let name = format_ident!("{}Instruction", ix.name.to_string().to_upper_camel_case());
             let mut data_len = Vec::new();
@@ -113,11 +128,11 @@ impl Generator for ClientGenerator {
                     InstructionArg::Context(ident) => {
                         let arg_ty = format_ident!("{ident}Context");
                         let context = context.get(&ident.to_string()).unwrap();
-                        accounts_len += context.accounts.len();
+                        accounts_len += context.flatten_accounts(&resolve).unwrap().len();
 
-                        if let Some(args) = &context.arguments {
+                        for (ctx_name, args) in context.flatten_arguments(&resolve).unwrap() {
                             let name = match args {
-                                Arguments::Values(_) => format_ident!("{ident}Args"),
+                                Arguments::Values(_) => format_ident!("{ctx_name}Args"),
                                 Arguments::Struct(struct_name) => struct_name.clone(),
                             };
                             data_len.push(quote!(core::mem::size_of::<#name>()));
@@ -130,7 +145,7 @@ impl Generator for ClientGenerator {
                     }
                 })
                 .unzip();
-            let dis: u8 = *discriminator as u8;
+            let dis_len = discriminator.len();
 
             token.extend(quote! {
                 pub struct #name {
@@ -140,10 +155,10 @@ impl Generator for ClientGenerator {
                 impl #name {
                     #[inline(always)]
                     pub fn into_instruction(self) -> ::solana_instruction::Instruction {
-                        let mut data = std::vec::Vec::with_capacity(1 #(+ #data_len)*);
+                        let mut data = std::vec::Vec::with_capacity(#dis_len #(+ #data_len)*);
                         let mut accounts = std::vec::Vec::with_capacity(#accounts_len);
 
-                        data.push(#dis);
+                        data.extend_from_slice(&[#(#discriminator),*]);
 
                         #(#assigns)*
 
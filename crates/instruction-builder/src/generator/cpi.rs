@@ -10,6 +10,8 @@ use {
 pub struct CpiGenerator;
 
 fn generate_ctx(ctxs: &hashbrown::HashMap<String, Context>) -> TokenStream {
+    let resolve = |name: &Ident| ctxs.get(&name.to_string());
+
     let tokens = ctxs.values().map(|ctx| {
         let name = &ctx.name;
         let ctx_name = format_ident!("{}Context", name);
@@ -24,14 +26,51 @@ fn generate_ctx(ctxs: &hashbrown::HashMap<String, Context>) -> TokenStream {
                 generate_arg((&format_ident!("args"), &parse_quote!(#arg_ty)))
             })
             .unzip();
-        let (acc_fields, metas, infos) = generate_accounts(&ctx.accounts);
+        let (mut acc_fields, metas, infos) = generate_accounts(&ctx.accounts);
         let arg_writer = args_assign.map(|el| quote!(writer.write_bytes(#el)?;));
-        let has_optional = ctx.accounts.iter().any(|acc| acc.meta.is_optional);
-        let program_field = if has_optional {
+        let program_field = if needs_program(ctx, &resolve) {
             Some(quote!(program: &'a AccountInfo,))
         } else {
             None
         };
+
+        for nested in ctx.nested.iter().rev() {
+            let name = &nested.name;
+            let nested_ctx = format_ident!("{}Context", nested.context);
+            acc_fields.insert(nested.index, quote!(pub #name: #nested_ctx<'a>,));
+        }
+
+        let mut writes = Vec::new();
+        let (mut start, mut offset) = (0, 0);
+        for nested in ctx.nested.iter().map(Some).chain([None]) {
+            let end = nested.map_or(ctx.accounts.len(), |nested| nested.index);
+            if start < end {
+                let (metas, infos) = (&metas[start..end], &infos[start..end]);
+                writes.push(quote! {
+                    for (d, s) in metas[#offset..].iter_mut().zip([#(#metas),*]) {
+                        d.write(s);
+                    }
+
+                    for (d, s) in infos[#offset..].iter_mut().zip([#(#infos),*]) {
+                        d.write(s);
+                    }
+                });
+                offset += end - start;
+            }
+            start = end;
+
+            if let Some(nested) = nested {
+                let name = &nested.name;
+                let nested_ctx = ctxs.get(&nested.context.to_string()).unwrap();
+                let next = offset + nested_ctx.flatten_accounts(&resolve).unwrap().len();
+                let program_arg = needs_program(nested_ctx, &resolve).then(|| quote!(program,));
+                writes.push(quote! {
+                    self.#name.append(#program_arg writer, &mut metas[#offset..#next], &mut infos[#offset..#next])?;
+                });
+                offset = next;
+            }
+        }
+
         quote! {
             pub struct #ctx_name<'a> {
                 #(#acc_fields)*
@@ -48,14 +87,7 @@ fn generate_ctx(ctxs: &hashbrown::HashMap<String, Context>) -> TokenStream {
                     infos: &mut [core::mem::MaybeUninit<&'a AccountInfo>],
                 ) -> ProgramResult {
                     #arg_writer
-
-                    for (d, s) in metas.iter_mut().zip([#(#metas),*]) {
-                        d.write(s);
-                    }
-
-                    for (d, s) in infos.iter_mut().zip([#(#infos),*]) {
-                        d.write(s);
-                    }
+                    #(#writes)*
 
                     Ok(())
                 }
@@ -66,6 +98,15 @@ fn generate_ctx(ctxs: &hashbrown::HashMap<String, Context>) -> TokenStream {
     quote!(#(#tokens)*)
 }
 
+/// Whether the accounts are filled with the invoked program: the missing optional accounts and the
+/// ones constrained to the program address, like the `program` account of `#[event_cpi]`.
+fn needs_program<'a>(ctx: &'a Context, resolve: &impl Fn(&Ident) -> Option<&'a Context>) -> bool {
+    ctx.flatten_accounts(resolve)
+        .unwrap()
+        .iter()
+        .any(|acc| acc.meta.is_optional || acc.is_program_address())
+}
+
 fn generate_arg((name, ty): (&Ident, &Type)) -> (TokenStream, TokenStream) {
     (
         quote!(pub #name: &'a #ty,),
@@ -83,6 +124,19 @@ fn generate_accounts(
 
     for acc in accounts {
         let name = &acc.name;
+        // Filled with the invoked program, the field stays empty to keep the indexes of the nested
+        // contexts.
+        if acc.is_program_address() {
+            account_fields.push(TokenStream::new());
+            metas.push(quote!(instruction::AccountMeta::new(
+                program.key(),
+                false,
+                false
+            )));
+            infos.push(quote!(program));
+            continue;
+        }
+
         let is_optional = acc.meta.is_optional;
         let is_mutable = acc.meta.is_mutable;
         let is_signer = acc.meta.is_signer;
@@ -120,17 +174,19 @@ fn generate_accounts(
 
 impl Generator for CpiGenerator {
     fn generate_token(
-        instructions: &hashbrown::HashMap<usize, typhoon_syn::Instruction>,
+        instructions: &hashbrown::HashMap<Vec<u8>, typhoon_syn::Instruction>,
         context: &hashbrown::HashMap<String, typhoon_syn::Context>,
         extra_token: TokenStream,
     ) -> TokenStream {
         let mut token = TokenStream::new();
 
         token.extend(generate_ctx(context));
+
+        let resolve = |name: &Ident| context.get(&name.to_string());
         instructions.iter().for_each(|(discriminator, ix)| {
             let instruction_name =
                 format_ident!("{}Cpi", ix.name.to_string().to_upper_camel_case());
-            let dis = *discriminator as u8;
+            let dis_len = discriminator.len();
             let (result_ty, return_data) = if let InstructionReturnData { ty: Some(ref ty), .. } = ix.return_data {
                 (
                     Some(quote!(<#ty>)),
@@ -145,7 +201,7 @@ impl Generator for CpiGenerator {
             };
             let mut data_len = Vec::new();
             let mut accumulated_len = 0;
-            let mut  has_optional = false;
+            let mut needs_program = false;
             let (fields, assigns): (Vec<_>, Vec<_>) = ix.args.iter().map(|(arg_name,v)| {
                 match v {
                     InstructionArg::Type { ty, .. } => {
@@ -157,13 +213,23 @@ impl Generator for CpiGenerator {
                     },
                     InstructionArg::Context(ctx_name) => {
                         let ctx = context.get(&ctx_name.to_string()).unwrap();
-                        let ctx_has_optional = ctx.accounts.iter().any(|acc| acc.meta.is_optional);
-                        if ctx_has_optional {
-                            has_optional = true;
+                        let ctx_accounts = ctx.flatten_accounts(&resolve).unwrap();
+                        let ctx_needs_program = ctx_accounts
+                            .iter()
+                            .any(|acc| acc.meta.is_optional || acc.is_program_address());
+                        if ctx_needs_program {
+                            needs_program = true;
                         }
-                        let program_arg = ctx_has_optional.then(|| quote!(self.program,));
+                        for (name, args) in ctx.flatten_arguments(&resolve).unwrap() {
+                            let arg_ty = match args {
+                                Arguments::Values(_) => format_ident!("{name}Args"),
+                                Arguments::Struct(ident) => ident.clone(),
+                            };
+                            data_len.push(quote!(core::mem::size_of::<#arg_ty>()));
+                        }
+                        let program_arg = ctx_needs_program.then(|| quote!(self.program,));
                         let ctx_struct = format_ident!("{ctx_name}Context");
-                        let acc_len = ctx.accounts.len();
+                        let acc_len = ctx_accounts.len();
                         let new_len = accumulated_len + acc_len;
                         let token = quote!(self.#arg_name.append(#program_arg &mut writer, &mut metas[#accumulated_len..#new_len], &mut infos[#accumulated_len..#new_len])?;);
                         accumulated_len = new_len;
@@ -171,7 +237,7 @@ impl Generator for CpiGenerator {
                     },
                 }
             }).unzip();
-            let (program_id_field, program_id_getter) = if has_optional {
+            let (program_id_field, program_id_getter) = if needs_program {
                 (quote!(&'a AccountInfo), Some(quote!(.key())))
             } else {
                 (quote!(&'a Pubkey), None)
@@ -191,11 +257,11 @@ impl Generator for CpiGenerator {
 
                     #[inline(always)]
                     pub fn invoke_signed(&self, seeds: &[instruction::CpiSigner]) -> ProgramResult #result_ty {
-                        let mut bytes = [bytes::UNINIT_BYTE; 1 #(+ #data_len)*];
+                        let mut bytes = [bytes::UNINIT_BYTE; #dis_len #(+ #data_len)*];
                         let mut metas = [bytes::UNINIT_META; #accumulated_len];
                         let mut infos = [bytes::UNINIT_INFO; #accumulated_len];
                         let mut writer = bytes::MaybeUninitWriter::new(&mut bytes, 0);
-                        writer.write_bytes(&[#dis])?;
+                        writer.write_bytes(&[#(#discriminator),*])?;
 
                         #(#assigns)*
 
@@ -221,3 +287,34 @@ impl Generator for CpiGenerator {
         token
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, syn::Item};
+
+    #[test]
+    fn event_cpi_with_optional_account() {
+        let item: syn::ItemStruct = parse_quote! {
+            #[event_cpi]
+            pub struct Emit {
+                pub payer: Mut<Signer>,
+                pub vault: Option<Mut<SystemAccount>>,
+            }
+        };
+        let mut ctxs = hashbrown::HashMap::new();
+        ctxs.insert("Emit".to_string(), Context::try_from(&item).unwrap());
+
+        let file: syn::File = syn::parse2(generate_ctx(&ctxs)).unwrap();
+        let Some(Item::Struct(ctx_struct)) = file.items.first() else {
+            panic!("Expected the context struct.");
+        };
+        let fields: Vec<String> = ctx_struct
+            .fields
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect();
+
+        // The `program` account of `#[event_cpi]` is filled with the invoked program.
+        assert_eq!(fields, ["payer", "vault", "event_authority"]);
+    }
+}
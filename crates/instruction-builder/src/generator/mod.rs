@@ -10,7 +10,7 @@ use {
 
 pub trait Generator {
     fn generate_token(
-        instructions: &HashMap<usize, Instruction>,
+        instructions: &HashMap<Vec<u8>, Instruction>,
         context: &HashMap<String, Context>,
         extra_token: TokenStream,
     ) -> TokenStream;
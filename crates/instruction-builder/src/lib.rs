@@ -9,14 +9,8 @@ use {
     proc_macro2::{Span, TokenStream},
     quote::{format_ident, quote, ToTokens},
     std::path::Path,
-    syn::{
-        parse::{Parse, Parser},
-        parse_macro_input,
-        punctuated::Punctuated,
-        visit::Visit,
-        Ident, Item, Token,
-    },
-    typhoon_syn::{Argument, Arguments, Context, Instruction, InstructionArg},
+    syn::{parse::Parse, parse_macro_input, visit::Visit, Ident, Item, Token},
+    typhoon_syn::{Argument, Arguments, Context, Instruction, InstructionArg, InstructionsList},
 };
 
 mod generator;
@@ -44,35 +38,19 @@ pub fn generate_cpi_client(input: proc_macro::TokenStream) -> proc_macro::TokenS
 }
 
 #[derive(Default)]
-struct InstructionsList(pub Vec<(usize, Ident)>);
+struct HandlersVisitor(Option<syn::Result<InstructionsList>>);
 
-impl Visit<'_> for InstructionsList {
+impl Visit<'_> for HandlersVisitor {
     fn visit_item_macro(&mut self, i: &syn::ItemMacro) {
-        if !i.mac.path.is_ident("handlers") {
-            return;
+        if i.mac.path.is_ident("handlers") {
+            self.0 = Some(InstructionsList::try_from(i));
         }
-
-        if let Ok(instructions) =
-            Punctuated::<Ident, syn::Token![,]>::parse_terminated.parse2(i.mac.tokens.clone())
-        {
-            self.0 = instructions
-                .iter()
-                .enumerate()
-                .map(|(i, n)| (i, n.clone()))
-                .collect()
-        };
-    }
-}
-
-impl From<InstructionsList> for Vec<(usize, Ident)> {
-    fn from(value: InstructionsList) -> Self {
-        value.0
     }
 }
 
 #[derive(Default)]
 struct GeneratorContext {
-    pub instructions: HashMap<usize, Instruction>,
+    pub instructions: HashMap<Vec<u8>, Instruction>,
     pub context: HashMap<String, Context>,
     pub arg_structs: HashMap<String, Vec<Argument>>,
 }
@@ -86,7 +64,7 @@ impl GeneratorContext {
         let mut gen = GeneratorContext::default();
         let (mut instructions_map, mut contexts_map) = Self::parse_items(&resolver.items);
 
-        for (index, ident) in ix_list.0 {
+        for (discriminator, ident) in ix_list.0 {
             if let Some(ref filter_set) = filter {
                 if !filter_set.contains(&ident) {
                     continue;
@@ -94,24 +72,30 @@ impl GeneratorContext {
             }
 
             if let Some(instruction) = instructions_map.remove(&ident) {
-                gen.instructions.insert(index, instruction);
+                gen.instructions.insert(discriminator, instruction);
             }
         }
 
-        for ix in gen.instructions.values() {
-            for (_, arg_value) in &ix.args {
-                if let InstructionArg::Context(ctx_name) = arg_value {
-                    let ctx_name = ctx_name.to_string();
-                    if let Some(context) = contexts_map.remove(&ctx_name) {
-                        if let Some(Arguments::Values(ref args)) = context.arguments {
-                            gen.arg_structs
-                                .entry(format!("{}Args", context.name))
-                                .or_insert_with(|| args.to_vec());
-                        }
-
-                        gen.context.insert(ctx_name, context);
-                    }
+        let mut ctx_names: Vec<String> = gen
+            .instructions
+            .values()
+            .flat_map(|ix| &ix.args)
+            .filter_map(|(_, arg_value)| match arg_value {
+                InstructionArg::Context(ctx_name) => Some(ctx_name.to_string()),
+                InstructionArg::Type { .. } => None,
+            })
+            .collect();
+
+        while let Some(ctx_name) = ctx_names.pop() {
+            if let Some(context) = contexts_map.remove(&ctx_name) {
+                if let Some(Arguments::Values(ref args)) = context.arguments {
+                    gen.arg_structs
+                        .entry(format!("{}Args", context.name))
+                        .or_insert_with(|| args.to_vec());
                 }
+
+                ctx_names.extend(context.nested.iter().map(|el| el.context.to_string()));
+                gen.context.insert(ctx_name, context);
             }
         }
         gen
@@ -177,8 +161,9 @@ impl Parse for GeneratorContext {
         let path = Path::new(&package_absolute_path);
         let file = read_and_parse_file(path)?;
 
-        let mut ix_list = InstructionsList::default();
-        ix_list.visit_file(&file);
+        let mut handlers = HandlersVisitor::default();
+        handlers.visit_file(&file);
+        let ix_list = handlers.0.transpose()?.unwrap_or_default();
 
         let mut resolver = Resolver::new(path, true);
         resolver.visit_file(&file);
@@ -5,7 +5,8 @@ use typhoon_errors::Error;
 pub mod macros {
     pub use {
         typhoon_account_macro::*, typhoon_context_macro::*, typhoon_cpi_generator_macro::*,
-        typhoon_errors_macro::*, typhoon_handler_macro::*, typhoon_program_id_macro::*,
+        typhoon_errors_macro::*, typhoon_event_macro::*, typhoon_handler_macro::*,
+        typhoon_program_id_macro::*,
     };
 }
 
@@ -19,6 +20,10 @@ pub mod bytes {
     pub use typhoon_utility::bytes::*;
 }
 
+pub mod signature {
+    pub use typhoon_utility::signature::*;
+}
+
 pub mod instruction {
     pub use pinocchio::instruction::{AccountMeta, Instruction, Seed, Signer as CpiSigner};
 }
@@ -29,7 +34,7 @@ pub mod prelude {
     #[cfg(not(feature = "std"))]
     pub use pinocchio::nostd_panic_handler;
     pub use {
-        super::{bytes, instruction, lib::*, macros::*, ProgramResult},
+        super::{bytes, instruction, lib::*, macros::*, signature, ProgramResult},
         pinocchio::{
             self,
             account_info::AccountInfo,
@@ -41,5 +46,6 @@ pub mod prelude {
             sysvars::{clock::Clock, fees::Fees, rent::Rent, Sysvar},
         },
         pinocchio_pubkey::{declare_id, from_str as pubkey_from_str},
+        typhoon_accounts::Sysvar as SysvarAccount,
     };
 }
@@ -1,5 +1,8 @@
 use {
-    crate::{constraints::Constraints, Docs},
+    crate::{
+        constraints::{Constraint, Constraints},
+        Docs,
+    },
     proc_macro2::Span,
     syn::{
         parse_quote,
@@ -30,6 +33,52 @@ impl InstructionAccount {
         let inner_ty = &self.inner_ty;
         self.ty.clone().unwrap_or(parse_quote!(#inner_ty<'info>))
     }
+
+    /// Returns `true` for `Sysvar<T>` accounts, named `SysvarAccount<T>` in the prelude.
+    pub fn is_sysvar(&self) -> bool {
+        self.ty
+            .as_ref()
+            .is_some_and(|ty| ty.ident == "Sysvar" || ty.ident == "SysvarAccount")
+    }
+
+    /// Returns the address of the account when it is known at compile time.
+    pub fn fixed_address(&self) -> Option<String> {
+        if self.is_sysvar() {
+            if let Some(address) = sysvar_address(&self.inner_ty.to_string()) {
+                return Some(address.to_string());
+            }
+        }
+
+        self.constraints
+            .0
+            .iter()
+            .find_map(|constraint| match constraint {
+                Constraint::Address(address) => address.literal(),
+                _ => None,
+            })
+    }
+
+    /// Returns `true` when the account is constrained to the program itself.
+    pub fn is_program_address(&self) -> bool {
+        self.constraints
+            .0
+            .iter()
+            .any(|constraint| match constraint {
+                Constraint::Address(address) => address.is_program_id(),
+                _ => false,
+            })
+    }
+}
+
+fn sysvar_address(name: &str) -> Option<&'static str> {
+    match name {
+        "Clock" => Some("SysvarC1ock11111111111111111111111111111111"),
+        "Instructions" => Some("Sysvar1nstructions1111111111111111111111111"),
+        "Rent" => Some("SysvarRent111111111111111111111111111111111"),
+        "SlotHashes" => Some("SysvarS1otHashes111111111111111111111111111"),
+        "StakeHistory" => Some("SysvarStakeHistory1111111111111111111111111"),
+        _ => None,
+    }
 }
 
 impl TryFrom<&Field> for InstructionAccount {
@@ -109,6 +158,19 @@ mod tests {
         assert!(!account.meta.is_optional);
         assert!(!account.meta.is_signer);
 
+        let field: syn::Field = parse_quote!(pub instructions: Sysvar<'info, Instructions>);
+        let account = InstructionAccount::try_from(&field).unwrap();
+        assert_eq!(account.inner_ty, "Instructions");
+        assert!(account.is_sysvar());
+        assert_eq!(
+            account.fixed_address().as_deref(),
+            Some("Sysvar1nstructions1111111111111111111111111")
+        );
+
+        let field: syn::Field = parse_quote!(pub clock: SysvarAccount<Clock>);
+        let account = InstructionAccount::try_from(&field).unwrap();
+        assert!(account.is_sysvar());
+
         let field: syn::Field = parse_quote!(pub random2: UncheckedAccount);
         let account = InstructionAccount::try_from(&field).unwrap();
         assert_eq!(account.inner_ty, "UncheckedAccount");
@@ -0,0 +1,83 @@
+use {
+    super::parse_error,
+    syn::{parse::Parse, Expr, Lit, LitStr, Token},
+};
+
+#[derive(Clone)]
+pub struct ConstraintAddress {
+    pub address: Expr,
+    pub error: Option<Expr>,
+}
+
+impl ConstraintAddress {
+    /// Returns the base58 address when it is written as a literal, e.g. `pubkey!("...")`.
+    pub fn literal(&self) -> Option<String> {
+        literal_address(&self.address)
+    }
+
+    /// Returns `true` when the address is the id of the program itself, e.g. `crate::ID`.
+    pub fn is_program_id(&self) -> bool {
+        let Expr::Path(path) = &self.address else {
+            return false;
+        };
+        let segments: Vec<String> = path
+            .path
+            .segments
+            .iter()
+            .map(|seg| seg.ident.to_string())
+            .collect();
+
+        match segments.as_slice() {
+            [id] => id == "ID",
+            [krate, id] => krate == "crate" && id == "ID",
+            _ => false,
+        }
+    }
+}
+
+fn literal_address(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Paren(paren) => literal_address(&paren.expr),
+        Expr::Group(group) => literal_address(&group.expr),
+        Expr::Macro(expr_macro) => {
+            let name = &expr_macro.mac.path.segments.last()?.ident;
+            if name != "pubkey" {
+                return None;
+            }
+
+            expr_macro
+                .mac
+                .parse_body::<LitStr>()
+                .ok()
+                .map(|lit| lit.value())
+        }
+        Expr::Call(call) => {
+            let Expr::Path(func) = call.func.as_ref() else {
+                return None;
+            };
+            let name = func.path.segments.last()?.ident.to_string();
+            if !matches!(name.as_str(), "from_str" | "pubkey_from_str") || call.args.len() != 1 {
+                return None;
+            }
+
+            match call.args.first()? {
+                Expr::Lit(expr_lit) => match &expr_lit.lit {
+                    Lit::Str(lit) => Some(lit.value()),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+impl Parse for ConstraintAddress {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![=]>()?;
+        let address = input.parse()?;
+        let error = parse_error(input)?;
+
+        Ok(ConstraintAddress { address, error })
+    }
+}
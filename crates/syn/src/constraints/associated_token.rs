@@ -1,9 +1,13 @@
-use syn::{parse::Parse, Ident, Token};
+use {
+    super::parse_error,
+    syn::{parse::Parse, Expr, Ident, Token},
+};
 
 #[derive(Clone)]
 pub enum ConstraintAssociatedToken {
-    Mint(Ident),
-    Authority(Ident),
+    Mint(Ident, Option<Expr>),
+    Authority(Ident, Option<Expr>),
+    TokenProgram(Ident),
 }
 
 impl Parse for ConstraintAssociatedToken {
@@ -13,13 +17,23 @@ impl Parse for ConstraintAssociatedToken {
         match name.as_str() {
             "mint" => {
                 input.parse::<Token![=]>()?;
+                let value = input.parse()?;
 
-                Ok(ConstraintAssociatedToken::Mint(input.parse()?))
+                Ok(ConstraintAssociatedToken::Mint(value, parse_error(input)?))
             }
             "authority" => {
                 input.parse::<Token![=]>()?;
+                let value = input.parse()?;
 
-                Ok(ConstraintAssociatedToken::Authority(input.parse()?))
+                Ok(ConstraintAssociatedToken::Authority(
+                    value,
+                    parse_error(input)?,
+                ))
+            }
+            "token_program" => {
+                input.parse::<Token![=]>()?;
+
+                Ok(ConstraintAssociatedToken::TokenProgram(input.parse()?))
             }
             _ => Err(syn::Error::new(
                 input.span(),
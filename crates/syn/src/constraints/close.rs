@@ -0,0 +1,18 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, Token,
+};
+
+#[derive(Clone)]
+pub struct ConstraintClose {
+    pub destination: Ident,
+}
+
+impl Parse for ConstraintClose {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![=]>()?;
+        let destination = input.parse()?;
+
+        Ok(ConstraintClose { destination })
+    }
+}
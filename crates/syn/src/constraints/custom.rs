@@ -0,0 +1,40 @@
+use {
+    super::parse_error,
+    syn::{
+        parenthesized,
+        parse::{Parse, ParseStream},
+        parse_quote,
+        punctuated::Punctuated,
+        Expr, Ident, Token,
+    },
+};
+
+/// A boolean check evaluated once the account has been validated, either an expression,
+/// `constraint = vault.amount >= args.min @ MyError`, or a named check,
+/// `constraint::min_amount(&vault, args.min) @ MyError`, calling the user defined
+/// `fn min_amount(..) -> bool`.
+#[derive(Clone)]
+pub struct ConstraintCustom {
+    pub expr: Expr,
+    pub error: Option<Expr>,
+}
+
+impl Parse for ConstraintCustom {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = if input.peek(Token![::]) {
+            input.parse::<Token![::]>()?;
+            let name: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let args = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+
+            parse_quote!(#name(#args))
+        } else {
+            input.parse::<Token![=]>()?;
+            input.parse()?
+        };
+        let error = parse_error(input)?;
+
+        Ok(ConstraintCustom { expr, error })
+    }
+}
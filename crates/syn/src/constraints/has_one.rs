@@ -1,4 +1,7 @@
-use syn::{parse::Parse, Expr, Ident, Token};
+use {
+    super::parse_error,
+    syn::{parse::Parse, Expr, Ident, Token},
+};
 
 #[derive(Clone)]
 pub struct ConstraintHasOne {
@@ -10,12 +13,7 @@ impl Parse for ConstraintHasOne {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         input.parse::<Token![=]>()?;
         let join_target = input.parse()?;
-        let error = if input.peek(Token![@]) {
-            input.parse::<Token![@]>()?;
-            Some(input.parse()?)
-        } else {
-            None
-        };
+        let error = parse_error(input)?;
 
         Ok(ConstraintHasOne { join_target, error })
     }
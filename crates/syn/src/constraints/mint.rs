@@ -1,10 +1,20 @@
-use syn::{parse::Parse, Expr, Ident, Token};
+use {
+    super::parse_error,
+    syn::{
+        parenthesized,
+        parse::{Parse, ParseStream},
+        punctuated::Punctuated,
+        Expr, Ident, Token,
+    },
+};
 
 #[derive(Clone)]
 pub enum ConstraintMint {
-    Authority(Expr),
-    Decimals(Expr),
-    FreezeAuthority(Expr),
+    Authority(Expr, Option<Expr>),
+    Decimals(Expr, Option<Expr>),
+    FreezeAuthority(Expr, Option<Expr>),
+    TokenProgram(Ident),
+    Extension(Box<ConstraintMintExtension>),
 }
 
 impl Parse for ConstraintMint {
@@ -14,22 +24,193 @@ impl Parse for ConstraintMint {
         match name.as_str() {
             "authority" => {
                 input.parse::<Token![=]>()?;
+                let value = input.parse()?;
 
-                Ok(ConstraintMint::Authority(input.parse()?))
+                Ok(ConstraintMint::Authority(value, parse_error(input)?))
             }
             "decimals" => {
                 input.parse::<Token![=]>()?;
+                let value = input.parse()?;
 
-                Ok(ConstraintMint::Decimals(input.parse()?))
+                Ok(ConstraintMint::Decimals(value, parse_error(input)?))
             }
             "freeze_authority" => {
                 input.parse::<Token![=]>()?;
-                Ok(ConstraintMint::FreezeAuthority(input.parse()?))
+                let value = input.parse()?;
+
+                Ok(ConstraintMint::FreezeAuthority(value, parse_error(input)?))
+            }
+            "token_program" => {
+                input.parse::<Token![=]>()?;
+
+                Ok(ConstraintMint::TokenProgram(input.parse()?))
+            }
+            "extensions" => {
+                input.parse::<Token![::]>()?;
+
+                Ok(ConstraintMint::Extension(Box::new(input.parse()?)))
             }
             _ => Err(syn::Error::new(
                 input.span(),
-                "Invalid variant for the token constraint.",
+                "Invalid variant for the mint constraint.",
+            )),
+        }
+    }
+}
+
+/// Token-2022 extension initialized on the mint, e.g. `mint::extensions::transfer_fee(..)`.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub enum ConstraintMintExtension {
+    CloseAuthority {
+        authority: Option<Expr>,
+    },
+    TransferFee {
+        config_authority: Option<Expr>,
+        withdraw_withheld_authority: Option<Expr>,
+        basis_points: Expr,
+        maximum_fee: Expr,
+    },
+    DefaultAccountState {
+        state: Expr,
+    },
+    NonTransferable,
+    InterestBearing {
+        rate_authority: Option<Expr>,
+        rate: Expr,
+    },
+    PermanentDelegate {
+        delegate: Expr,
+    },
+    TransferHook {
+        authority: Option<Expr>,
+        program_id: Option<Expr>,
+    },
+    MetadataPointer {
+        authority: Option<Expr>,
+        metadata_address: Option<Expr>,
+    },
+    GroupPointer {
+        authority: Option<Expr>,
+        group_address: Option<Expr>,
+    },
+    GroupMemberPointer {
+        authority: Option<Expr>,
+        member_address: Option<Expr>,
+    },
+    Pausable {
+        authority: Expr,
+    },
+}
+
+struct ExtensionArg {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for ExtensionArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+
+        Ok(ExtensionArg { name, value })
+    }
+}
+
+struct ExtensionArgs {
+    extension: Ident,
+    args: Vec<ExtensionArg>,
+}
+
+impl ExtensionArgs {
+    fn parse(extension: Ident, input: ParseStream) -> syn::Result<Self> {
+        let args = if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::<ExtensionArg, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(ExtensionArgs { extension, args })
+    }
+
+    fn optional(&mut self, name: &str) -> Option<Expr> {
+        let index = self.args.iter().position(|arg| arg.name == name)?;
+        Some(self.args.remove(index).value)
+    }
+
+    fn required(&mut self, name: &str) -> syn::Result<Expr> {
+        self.optional(name).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &self.extension,
+                format!("The `{name}` argument is required for this extension."),
+            )
+        })
+    }
+
+    fn finish(self, extension: ConstraintMintExtension) -> syn::Result<ConstraintMintExtension> {
+        match self.args.first() {
+            Some(arg) => Err(syn::Error::new_spanned(
+                &arg.name,
+                "Unknown argument for this extension.",
             )),
+            None => Ok(extension),
         }
     }
 }
+
+impl Parse for ConstraintMintExtension {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+        let mut args = ExtensionArgs::parse(name.clone(), input)?;
+
+        let extension = match name.to_string().as_str() {
+            "close_authority" => ConstraintMintExtension::CloseAuthority {
+                authority: args.optional("authority"),
+            },
+            "transfer_fee" => ConstraintMintExtension::TransferFee {
+                config_authority: args.optional("config_authority"),
+                withdraw_withheld_authority: args.optional("withdraw_withheld_authority"),
+                basis_points: args.required("basis_points")?,
+                maximum_fee: args.required("maximum_fee")?,
+            },
+            "default_account_state" => ConstraintMintExtension::DefaultAccountState {
+                state: args.required("state")?,
+            },
+            "non_transferable" => ConstraintMintExtension::NonTransferable,
+            "interest_bearing" => ConstraintMintExtension::InterestBearing {
+                rate_authority: args.optional("rate_authority"),
+                rate: args.required("rate")?,
+            },
+            "permanent_delegate" => ConstraintMintExtension::PermanentDelegate {
+                delegate: args.required("delegate")?,
+            },
+            "transfer_hook" => ConstraintMintExtension::TransferHook {
+                authority: args.optional("authority"),
+                program_id: args.optional("program_id"),
+            },
+            "metadata_pointer" => ConstraintMintExtension::MetadataPointer {
+                authority: args.optional("authority"),
+                metadata_address: args.optional("metadata_address"),
+            },
+            "group_pointer" => ConstraintMintExtension::GroupPointer {
+                authority: args.optional("authority"),
+                group_address: args.optional("group_address"),
+            },
+            "group_member_pointer" => ConstraintMintExtension::GroupMemberPointer {
+                authority: args.optional("authority"),
+                member_address: args.optional("member_address"),
+            },
+            "pausable" => ConstraintMintExtension::Pausable {
+                authority: args.required("authority")?,
+            },
+            _ => return Err(syn::Error::new_spanned(name, "Unknown mint extension.")),
+        };
+
+        args.finish(extension)
+    }
+}
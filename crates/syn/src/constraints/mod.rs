@@ -1,29 +1,35 @@
 use syn::{
     parse::{Parse, ParseStream},
-    Ident, Token,
+    Expr, Ident, Token,
 };
 
+mod address;
 mod associated_token;
 mod bump;
+mod close;
+mod custom;
 mod has_one;
 mod init;
 mod init_if_needed;
 mod mint;
+mod multisig;
+mod owner;
 mod payer;
 mod program;
+mod realloc;
 mod seeded;
 mod seeds;
 mod space;
 mod token;
 
 pub use {
-    associated_token::*, bump::*, has_one::*, init::*, init_if_needed::*, mint::*, payer::*,
-    program::*, seeded::*, seeds::*, space::*, token::*,
+    address::*, associated_token::*, bump::*, close::*, custom::*, has_one::*, init::*,
+    init_if_needed::*, mint::*, multisig::*, owner::*, payer::*, program::*, realloc::*, seeded::*,
+    seeds::*, space::*, token::*,
 };
 
 pub const CONSTRAINT_IDENT_STR: &str = "constraint";
 
-//TODO rewrite it to add custom constraint for users
 #[derive(Clone)]
 pub enum Constraint {
     Init(ConstraintInit),
@@ -38,6 +44,12 @@ pub enum Constraint {
     Mint(ConstraintMint),
     AssociatedToken(ConstraintAssociatedToken),
     InitIfNeeded(ConstraintInitIfNeeded),
+    Close(ConstraintClose),
+    Realloc(ConstraintRealloc),
+    Custom(ConstraintCustom),
+    Address(ConstraintAddress),
+    Owner(ConstraintOwner),
+    Multisig(ConstraintMultisig),
 }
 
 #[derive(Clone, Default)]
@@ -60,6 +72,16 @@ impl TryFrom<&[syn::Attribute]> for Constraints {
     }
 }
 
+/// Parses the optional `@ error` suffix used to override the error of a constraint.
+pub(crate) fn parse_error(input: ParseStream) -> syn::Result<Option<Expr>> {
+    if input.peek(Token![@]) {
+        input.parse::<Token![@]>()?;
+        Ok(Some(input.parse()?))
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn parse_constraints(input: ParseStream) -> syn::Result<Vec<Constraint>> {
     let mut constraints = Vec::new();
 
@@ -80,7 +102,15 @@ pub fn parse_constraints(input: ParseStream) -> syn::Result<Vec<Constraint>> {
                 ConstraintAssociatedToken::parse(input)?,
             )),
             "init_if_needed" => constraints.push(Constraint::InitIfNeeded(ConstraintInitIfNeeded)),
-            _ => return Err(syn::Error::new(input.span(), "Unknow constraint.")),
+            "close" => constraints.push(Constraint::Close(ConstraintClose::parse(input)?)),
+            "realloc" => constraints.push(Constraint::Realloc(ConstraintRealloc::parse(input)?)),
+            "address" => constraints.push(Constraint::Address(ConstraintAddress::parse(input)?)),
+            "owner" => constraints.push(Constraint::Owner(ConstraintOwner::parse(input)?)),
+            "multisig" => constraints.push(Constraint::Multisig(ConstraintMultisig::parse(input)?)),
+            CONSTRAINT_IDENT_STR => {
+                constraints.push(Constraint::Custom(ConstraintCustom::parse(input)?))
+            }
+            _ => return Err(syn::Error::new(input.span(), "Unknown constraint.")),
         }
 
         if input.peek(Token![,]) {
@@ -93,7 +123,7 @@ pub fn parse_constraints(input: ParseStream) -> syn::Result<Vec<Constraint>> {
 
 #[cfg(test)]
 mod tests {
-    use {super::*, syn::parse_quote};
+    use {super::*, quote::ToTokens, syn::parse_quote};
 
     #[test]
     fn test_parse_constraints() {
@@ -102,19 +132,205 @@ mod tests {
                 has_one = account,
                 seeds = [
                     b"seed".as_ref(),
-                ],
+                ] @ ProgramError::InvalidSeeds,
                 bump = counter.data()?.bump,
-                token::mint = mint,
+                token::mint = mint @ MyError::InvalidMint,
                 token::owner = authority,
                 mint::decimals = args.decimals,
                 mint::authority = escrow.key(),
                 mint::freeze_authority = freeze_authority.key(),
-                init_if_needed
+                init_if_needed,
+                close = payer,
+                realloc = 8 + args.len as usize,
+                realloc::payer = payer,
+                realloc::zero = true
+            )]
+        };
+
+        let constraints = Constraints::try_from(attributes.as_slice()).unwrap();
+
+        assert_eq!(constraints.0.len(), 13);
+        assert!(matches!(
+            constraints.0[1],
+            Constraint::Seeds(ConstraintSeeds { error: Some(_), .. })
+        ));
+        assert!(matches!(
+            constraints.0[3],
+            Constraint::Token(ConstraintToken::Mint(_, Some(_)))
+        ));
+        assert!(matches!(
+            constraints.0[4],
+            Constraint::Token(ConstraintToken::Owner(_, None))
+        ));
+
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(realloc::zero = false)]
+        };
+        assert!(Constraints::try_from(attributes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_parse_token_state_constraints() {
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(
+                token::delegate = delegate @ MyError::InvalidDelegate,
+                token::close_authority = authority,
+                token::is_frozen = false,
+                token::native @ MyError::NotWrappedSol
+            )]
+        };
+
+        let constraints = Constraints::try_from(attributes.as_slice()).unwrap();
+
+        assert!(matches!(
+            constraints.0[0],
+            Constraint::Token(ConstraintToken::Delegate(_, Some(_)))
+        ));
+        assert!(matches!(
+            constraints.0[1],
+            Constraint::Token(ConstraintToken::CloseAuthority(_, None))
+        ));
+        assert!(matches!(
+            constraints.0[2],
+            Constraint::Token(ConstraintToken::IsFrozen(_, None))
+        ));
+        assert!(matches!(
+            constraints.0[3],
+            Constraint::Token(ConstraintToken::Native(Some(_)))
+        ));
+
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(multisig::signers @ MyError::NotEnoughSigners)]
+        };
+        let constraints = Constraints::try_from(attributes.as_slice()).unwrap();
+        assert!(matches!(
+            constraints.0[0],
+            Constraint::Multisig(ConstraintMultisig::Signers(Some(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_custom_constraints() {
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(
+                constraint = vault.amount >= args.min @ VaultError::NotEnoughFunds,
+                constraint = counter.count < 10,
+                constraint = is_not_empty(&vault),
+                constraint::min_amount(&vault, args.min) @ VaultError::NotEnoughFunds
+            )]
+        };
+        let constraints = Constraints::try_from(attributes.as_slice()).unwrap();
+
+        assert_eq!(constraints.0.len(), 4);
+        let Constraint::Custom(ref custom) = constraints.0[0] else {
+            panic!("Expected a custom constraint.");
+        };
+        assert!(custom.error.is_some());
+        assert!(matches!(constraints.0[2], Constraint::Custom(_)));
+        let Constraint::Custom(ref named) = constraints.0[3] else {
+            panic!("Expected a named constraint.");
+        };
+        assert_eq!(
+            named.expr.to_token_stream().to_string(),
+            "min_amount (& vault , args . min)"
+        );
+        assert!(named.error.is_some());
+
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(not_empty)]
+        };
+        assert!(Constraints::try_from(attributes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_parse_mint_extensions() {
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(
+                mint::token_program = token_program,
+                mint::extensions::transfer_fee(basis_points = 50, maximum_fee = args.max_fee),
+                mint::extensions::metadata_pointer(metadata_address = mint.key()),
+                mint::extensions::non_transferable
             )]
         };
 
         let constraints = Constraints::try_from(attributes.as_slice()).unwrap();
 
-        assert_eq!(constraints.0.len(), 9);
+        assert_eq!(constraints.0.len(), 4);
+        assert!(matches!(
+            constraints.0[0],
+            Constraint::Mint(ConstraintMint::TokenProgram(_))
+        ));
+        let extensions: Vec<&ConstraintMintExtension> = constraints
+            .0
+            .iter()
+            .filter_map(|el| match el {
+                Constraint::Mint(ConstraintMint::Extension(extension)) => Some(extension.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(extensions.len(), 3);
+        assert!(matches!(
+            extensions[0],
+            ConstraintMintExtension::TransferFee {
+                config_authority: None,
+                ..
+            }
+        ));
+        assert!(matches!(
+            extensions[1],
+            ConstraintMintExtension::MetadataPointer {
+                authority: None,
+                metadata_address: Some(_),
+            }
+        ));
+        assert!(matches!(
+            extensions[2],
+            ConstraintMintExtension::NonTransferable
+        ));
+
+        let missing_arg: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(mint::extensions::transfer_fee(basis_points = 50))]
+        };
+        assert!(Constraints::try_from(missing_arg.as_slice()).is_err());
+
+        let unknown_arg: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(mint::extensions::non_transferable(authority = payer.key()))]
+        };
+        assert!(Constraints::try_from(unknown_arg.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_parse_address_constraints() {
+        let attributes: Vec<syn::Attribute> = parse_quote! {
+            #[constraint(
+                address = pubkey!("SysvarRent111111111111111111111111111111111"),
+                address = pubkey_from_str("SysvarC1ock11111111111111111111111111111111") @ MyError::InvalidClock,
+                address = ADMIN,
+                owner = crate::ID @ MyError::InvalidOwner
+            )]
+        };
+
+        let constraints = Constraints::try_from(attributes.as_slice()).unwrap();
+        let addresses: Vec<Option<String>> = constraints
+            .0
+            .iter()
+            .filter_map(|el| match el {
+                Constraint::Address(address) => Some(address.literal()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            addresses,
+            vec![
+                Some("SysvarRent111111111111111111111111111111111".to_string()),
+                Some("SysvarC1ock11111111111111111111111111111111".to_string()),
+                None
+            ]
+        );
+        assert!(matches!(
+            constraints.0[3],
+            Constraint::Owner(ConstraintOwner { error: Some(_), .. })
+        ));
     }
 }
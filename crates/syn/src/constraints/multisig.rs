@@ -0,0 +1,24 @@
+use {
+    super::parse_error,
+    syn::{parse::Parse, Expr, Ident, Token},
+};
+
+#[derive(Clone)]
+pub enum ConstraintMultisig {
+    /// At least `m` signers of the multisig signed among the remaining accounts.
+    Signers(Option<Expr>),
+}
+
+impl Parse for ConstraintMultisig {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![::]>()?;
+        let name = input.parse::<Ident>()?.to_string();
+        match name.as_str() {
+            "signers" => Ok(ConstraintMultisig::Signers(parse_error(input)?)),
+            _ => Err(syn::Error::new(
+                input.span(),
+                "Invalid variant for the multisig constraint.",
+            )),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use {
+    super::parse_error,
+    syn::{parse::Parse, Expr, Token},
+};
+
+#[derive(Clone)]
+pub struct ConstraintOwner {
+    pub owner: Expr,
+    pub error: Option<Expr>,
+}
+
+impl Parse for ConstraintOwner {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![=]>()?;
+        let owner = input.parse()?;
+        let error = parse_error(input)?;
+
+        Ok(ConstraintOwner { owner, error })
+    }
+}
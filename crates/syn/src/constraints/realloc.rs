@@ -0,0 +1,45 @@
+use syn::{parse::Parse, Expr, Ident, LitBool, Token};
+
+#[derive(Clone)]
+pub enum ConstraintRealloc {
+    Size(Expr),
+    Payer(Ident),
+    /// New bytes are always zero-initialized, only `realloc::zero = true` is accepted.
+    Zero,
+}
+
+impl Parse for ConstraintRealloc {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+
+            return Ok(ConstraintRealloc::Size(input.parse()?));
+        }
+
+        input.parse::<Token![::]>()?;
+        let name = input.parse::<Ident>()?.to_string();
+        match name.as_str() {
+            "payer" => {
+                input.parse::<Token![=]>()?;
+
+                Ok(ConstraintRealloc::Payer(input.parse()?))
+            }
+            "zero" => {
+                input.parse::<Token![=]>()?;
+                let zero = input.parse::<LitBool>()?;
+                if !zero.value {
+                    return Err(syn::Error::new_spanned(
+                        zero,
+                        "The new bytes are always zero-initialized, `realloc::zero` can only be `true`.",
+                    ));
+                }
+
+                Ok(ConstraintRealloc::Zero)
+            }
+            _ => Err(syn::Error::new(
+                input.span(),
+                "Invalid variant for the realloc constraint.",
+            )),
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use {
+    super::parse_error,
     crate::utils::SeedsExpr,
     syn::{
         parse::{Parse, ParseStream},
-        Token,
+        Expr, Token,
     },
 };
 
 #[derive(Clone)]
 pub struct ConstraintSeeds {
     pub seeds: SeedsExpr,
+    pub error: Option<Expr>,
 }
 
 impl Parse for ConstraintSeeds {
@@ -16,6 +18,7 @@ impl Parse for ConstraintSeeds {
         input.parse::<Token![=]>()?;
 
         let seeds = input.parse()?;
-        Ok(ConstraintSeeds { seeds })
+        let error = parse_error(input)?;
+        Ok(ConstraintSeeds { seeds, error })
     }
 }
@@ -1,9 +1,16 @@
-use syn::{parse::Parse, Expr, Ident, Token};
+use {
+    super::parse_error,
+    syn::{parse::Parse, Expr, Ident, Token},
+};
 
 #[derive(Clone)]
 pub enum ConstraintToken {
-    Mint(Ident),
-    Owner(Expr),
+    Mint(Ident, Option<Expr>),
+    Owner(Expr, Option<Expr>),
+    Delegate(Expr, Option<Expr>),
+    CloseAuthority(Expr, Option<Expr>),
+    IsFrozen(Expr, Option<Expr>),
+    Native(Option<Expr>),
 }
 
 impl Parse for ConstraintToken {
@@ -13,14 +20,35 @@ impl Parse for ConstraintToken {
         match name.as_str() {
             "mint" => {
                 input.parse::<Token![=]>()?;
+                let value = input.parse()?;
 
-                Ok(ConstraintToken::Mint(input.parse()?))
+                Ok(ConstraintToken::Mint(value, parse_error(input)?))
             }
             "owner" => {
                 input.parse::<Token![=]>()?;
+                let value = input.parse()?;
 
-                Ok(ConstraintToken::Owner(input.parse()?))
+                Ok(ConstraintToken::Owner(value, parse_error(input)?))
             }
+            "delegate" => {
+                input.parse::<Token![=]>()?;
+                let value = input.parse()?;
+
+                Ok(ConstraintToken::Delegate(value, parse_error(input)?))
+            }
+            "close_authority" => {
+                input.parse::<Token![=]>()?;
+                let value = input.parse()?;
+
+                Ok(ConstraintToken::CloseAuthority(value, parse_error(input)?))
+            }
+            "is_frozen" => {
+                input.parse::<Token![=]>()?;
+                let value = input.parse()?;
+
+                Ok(ConstraintToken::IsFrozen(value, parse_error(input)?))
+            }
+            "native" => Ok(ConstraintToken::Native(parse_error(input)?)),
             _ => Err(syn::Error::new(
                 input.span(),
                 "Invalid variant for the token constraint.",
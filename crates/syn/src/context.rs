@@ -1,23 +1,160 @@
 use {
-    crate::{account::InstructionAccount, arguments::Arguments},
-    syn::{Ident, ItemStruct},
+    crate::{account::InstructionAccount, arguments::Arguments, constraints::CONSTRAINT_IDENT_STR},
+    syn::{parse_quote, Field, Fields, FieldsNamed, Ident, ItemStruct, Type},
 };
 
+pub const EVENT_CPI_IDENT_STR: &str = "event_cpi";
+pub const FLATTEN_IDENT_STR: &str = "flatten";
+
+/// Accounts injected by `#[event_cpi]` to emit events through a self-CPI.
+pub fn event_cpi_fields() -> Vec<Field> {
+    let fields: FieldsNamed = parse_quote!({
+        #[constraint(seeds = [EVENT_AUTHORITY_SEED], bump)]
+        pub event_authority: UncheckedAccount,
+        #[constraint(address = crate::ID)]
+        pub program: UncheckedAccount,
+    });
+
+    fields.named.into_iter().collect()
+}
+
+/// Field marked `#[flatten]`, the accounts of the nested `#[context]` are consumed in its place.
+#[derive(Clone)]
+pub struct NestedContext {
+    pub name: Ident,
+    pub context: Ident,
+    /// Number of accounts declared before the field.
+    pub index: usize,
+}
+
+impl NestedContext {
+    fn try_from_field(field: &Field, index: usize) -> syn::Result<Self> {
+        if field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident(CONSTRAINT_IDENT_STR))
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "A `#[flatten]` field cannot have constraints.",
+            ));
+        }
+
+        let context = match &field.ty {
+            Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last(),
+            _ => None,
+        }
+        .map(|seg| seg.ident.clone())
+        .filter(|ident| ident != "Option" && !ident.to_string().starts_with("Mut"))
+        .ok_or(syn::Error::new_spanned(
+            &field.ty,
+            "A `#[flatten]` field needs to be a `#[context]` struct.",
+        ))?;
+
+        Ok(NestedContext {
+            name: field
+                .ident
+                .clone()
+                .ok_or(syn::Error::new_spanned(field, "The field need to be named"))?,
+            context,
+            index,
+        })
+    }
+}
+
+/// Splits the fields of a context into its own accounts and the flattened contexts.
+pub fn parse_context_fields(
+    fields: &Fields,
+) -> syn::Result<(Vec<InstructionAccount>, Vec<NestedContext>)> {
+    let mut accounts = Vec::new();
+    let mut nested = Vec::new();
+
+    for field in fields {
+        if field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident(FLATTEN_IDENT_STR))
+        {
+            nested.push(NestedContext::try_from_field(field, accounts.len())?);
+        } else {
+            accounts.push(InstructionAccount::try_from(field)?);
+        }
+    }
+
+    Ok((accounts, nested))
+}
+
 pub struct Context {
     pub name: Ident,
     pub accounts: Vec<InstructionAccount>,
     pub arguments: Option<Arguments>,
+    pub nested: Vec<NestedContext>,
+}
+
+impl Context {
+    /// Accounts in the order they are consumed, the ones of the nested contexts included.
+    pub fn flatten_accounts<'a>(
+        &'a self,
+        resolve: &impl Fn(&Ident) -> Option<&'a Context>,
+    ) -> syn::Result<Vec<&'a InstructionAccount>> {
+        let mut accounts = Vec::with_capacity(self.accounts.len());
+        let mut start = 0;
+
+        for nested in &self.nested {
+            accounts.extend(&self.accounts[start..nested.index]);
+            accounts.append(&mut resolve_nested(nested, resolve)?.flatten_accounts(resolve)?);
+            start = nested.index;
+        }
+        accounts.extend(&self.accounts[start..]);
+
+        Ok(accounts)
+    }
+
+    /// Arguments in the order they are read: the ones of the context, then the ones of the
+    /// nested contexts.
+    pub fn flatten_arguments<'a>(
+        &'a self,
+        resolve: &impl Fn(&Ident) -> Option<&'a Context>,
+    ) -> syn::Result<Vec<(&'a Ident, &'a Arguments)>> {
+        let mut arguments: Vec<_> = self
+            .arguments
+            .iter()
+            .map(|args| (&self.name, args))
+            .collect();
+
+        for nested in &self.nested {
+            arguments.append(&mut resolve_nested(nested, resolve)?.flatten_arguments(resolve)?);
+        }
+
+        Ok(arguments)
+    }
+}
+
+fn resolve_nested<'a>(
+    nested: &NestedContext,
+    resolve: &impl Fn(&Ident) -> Option<&'a Context>,
+) -> syn::Result<&'a Context> {
+    resolve(&nested.context).ok_or(syn::Error::new_spanned(
+        &nested.context,
+        "Cannot find the flattened context.",
+    ))
 }
 
 impl TryFrom<&ItemStruct> for Context {
     type Error = syn::Error;
 
     fn try_from(value: &ItemStruct) -> Result<Self, Self::Error> {
-        let accounts = value
-            .fields
+        let (mut accounts, nested) = parse_context_fields(&value.fields)?;
+
+        if value
+            .attrs
             .iter()
-            .map(InstructionAccount::try_from)
-            .collect::<Result<_, _>>()?;
+            .any(|attr| attr.path().is_ident(EVENT_CPI_IDENT_STR))
+        {
+            for field in event_cpi_fields() {
+                accounts.push(InstructionAccount::try_from(&field)?);
+            }
+        }
 
         let arguments = value
             .attrs
@@ -29,6 +166,86 @@ impl TryFrom<&ItemStruct> for Context {
             name: value.ident.clone(),
             accounts,
             arguments,
+            nested,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_cpi_context() {
+        let item: ItemStruct = parse_quote! {
+            #[event_cpi]
+            pub struct Emit {
+                pub payer: Mut<Signer>,
+            }
+        };
+        let context = Context::try_from(&item).unwrap();
+        let names: Vec<String> = context
+            .accounts
+            .iter()
+            .map(|acc| acc.name.to_string())
+            .collect();
+
+        assert_eq!(names, ["payer", "event_authority", "program"]);
+        assert!(context.accounts[2].is_program_address());
+        assert!(!context.accounts[1].is_program_address());
+    }
+
+    #[test]
+    fn test_flatten_context() {
+        let common: ItemStruct = parse_quote! {
+            #[args(fee: u64)]
+            pub struct Common {
+                pub market: Mut<Account<Market>>,
+                pub oracle: UncheckedAccount,
+            }
+        };
+        let item: ItemStruct = parse_quote! {
+            #[args(amount: u64)]
+            pub struct Swap {
+                pub payer: Mut<Signer>,
+                #[flatten]
+                pub common: Common,
+                pub vault: Mut<TokenAccount>,
+            }
+        };
+        let common = Context::try_from(&common).unwrap();
+        let context = Context::try_from(&item).unwrap();
+
+        assert_eq!(context.accounts.len(), 2);
+        assert_eq!(context.nested.len(), 1);
+        assert_eq!(context.nested[0].name, "common");
+        assert_eq!(context.nested[0].context, "Common");
+        assert_eq!(context.nested[0].index, 1);
+
+        let resolve = |name: &Ident| (name == "Common").then_some(&common);
+        let names: Vec<String> = context
+            .flatten_accounts(&resolve)
+            .unwrap()
+            .iter()
+            .map(|acc| acc.name.to_string())
+            .collect();
+        assert_eq!(names, ["payer", "market", "oracle", "vault"]);
+
+        let args: Vec<String> = context
+            .flatten_arguments(&resolve)
+            .unwrap()
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+        assert_eq!(args, ["Swap", "Common"]);
+
+        let item: ItemStruct = parse_quote! {
+            pub struct Invalid {
+                #[flatten]
+                #[constraint(mut)]
+                pub common: Common,
+            }
+        };
+        assert!(Context::try_from(&item).is_err());
+    }
+}
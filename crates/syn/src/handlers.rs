@@ -0,0 +1,426 @@
+use {
+    crate::EVENT_CPI_IDENT_STR,
+    quote::ToTokens,
+    syn::{
+        parse::{Parse, ParseStream},
+        Attribute, Expr, Ident, Lit, Path, Token,
+    },
+    typhoon_discriminator::sighash,
+};
+
+pub const DISCRIMINATOR_IDENT_STR: &str = "discriminator";
+pub const ENTRYPOINT_IDENT_STR: &str = "entrypoint";
+pub const FALLBACK_IDENT_STR: &str = "fallback";
+pub const MARKERS_IDENT_STR: &str = "markers";
+
+/// Layout of the instruction discriminators, set with `#![discriminator(..)]` in `handlers!`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscriminatorMode {
+    /// One byte, the position of the handler in the list.
+    #[default]
+    Index,
+    /// Two bytes, the position of the handler as a little-endian `u16`.
+    U16,
+    /// `sha256("global:<name>")[..8]`, compatible with Anchor programs.
+    Anchor,
+}
+
+impl DiscriminatorMode {
+    pub fn discriminator(&self, index: usize, name: &Ident) -> Vec<u8> {
+        match self {
+            DiscriminatorMode::Index => vec![index as u8],
+            DiscriminatorMode::U16 => (index as u16).to_le_bytes().to_vec(),
+            DiscriminatorMode::Anchor => sighash("global", &name.to_string()).to_vec(),
+        }
+    }
+
+    /// Length of the discriminators derived by the mode.
+    pub fn discriminator_len(&self) -> usize {
+        match self {
+            DiscriminatorMode::Index => 1,
+            DiscriminatorMode::U16 => 2,
+            DiscriminatorMode::Anchor => 8,
+        }
+    }
+}
+
+impl TryFrom<&Attribute> for DiscriminatorMode {
+    type Error = syn::Error;
+
+    fn try_from(value: &Attribute) -> Result<Self, Self::Error> {
+        let mode: Ident = value.parse_args()?;
+        match mode.to_string().as_str() {
+            "index" => Ok(DiscriminatorMode::Index),
+            "u16" => Ok(DiscriminatorMode::U16),
+            "anchor" => Ok(DiscriminatorMode::Anchor),
+            _ => Err(syn::Error::new_spanned(
+                mode,
+                "Unknown discriminator mode, expected `index`, `u16` or `anchor`.",
+            )),
+        }
+    }
+}
+
+/// A handler, optionally routed with an explicit discriminator: `5 => deposit` or
+/// `[1, 2, 3] => deposit`.
+pub struct HandlerEntry {
+    pub discriminator: Option<Expr>,
+    pub path: Path,
+}
+
+impl HandlerEntry {
+    pub fn name(&self) -> syn::Result<&Ident> {
+        self.path
+            .segments
+            .last()
+            .map(|seg| &seg.ident)
+            .ok_or(syn::Error::new_spanned(&self.path, "Invalid handler path."))
+    }
+
+    fn span(&self) -> &dyn ToTokens {
+        match &self.discriminator {
+            Some(discriminator) => discriminator,
+            None => &self.path,
+        }
+    }
+}
+
+impl Parse for HandlerEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        let is_positional =
+            fork.parse::<Path>().is_ok() && (fork.is_empty() || fork.peek(Token![,]));
+
+        let discriminator = if is_positional {
+            None
+        } else {
+            let discriminator = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            Some(discriminator)
+        };
+
+        Ok(HandlerEntry {
+            discriminator,
+            path: input.parse()?,
+        })
+    }
+}
+
+/// Discriminator of a handler, the expression is kept when it is not a literal.
+pub enum HandlerDiscriminator<'a> {
+    Bytes(Vec<u8>),
+    Expr(&'a Expr),
+}
+
+/// Content of the `handlers!` macro.
+pub struct Handlers {
+    pub event_cpi: bool,
+    /// Reads the accounts on demand with the lazy entrypoint of pinocchio: `#![entrypoint(lazy)]`.
+    pub lazy: bool,
+    /// Emits the `discriminators` module of instruction marker types: `#![markers]`.
+    pub markers: bool,
+    pub mode: DiscriminatorMode,
+    pub entries: Vec<HandlerEntry>,
+    /// Called with the raw instruction data when no discriminator matches: `fallback = my_fn`.
+    pub fallback: Option<Path>,
+}
+
+impl Parse for Handlers {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut event_cpi = false;
+        let mut lazy = false;
+        let mut markers = false;
+        let mut mode = DiscriminatorMode::default();
+        for attr in input.call(Attribute::parse_inner)? {
+            if attr.path().is_ident(EVENT_CPI_IDENT_STR) {
+                event_cpi = true;
+            } else if attr.path().is_ident(DISCRIMINATOR_IDENT_STR) {
+                mode = DiscriminatorMode::try_from(&attr)?;
+            } else if attr.path().is_ident(MARKERS_IDENT_STR) {
+                attr.meta.require_path_only()?;
+                markers = true;
+            } else if attr.path().is_ident(ENTRYPOINT_IDENT_STR) {
+                let entrypoint: Ident = attr.parse_args()?;
+                if entrypoint != "lazy" {
+                    return Err(syn::Error::new_spanned(
+                        entrypoint,
+                        "Unknown entrypoint, expected `lazy`.",
+                    ));
+                }
+                lazy = true;
+            } else {
+                return Err(syn::Error::new_spanned(attr, "Unknown handlers attribute."));
+            }
+        }
+
+        let mut entries = Vec::new();
+        let mut fallback = None;
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![=>]) {
+                let name = input.parse::<Ident>()?;
+                if name != FALLBACK_IDENT_STR {
+                    return Err(syn::Error::new_spanned(name, "Unknown handlers option."));
+                }
+                if fallback.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "The fallback is already defined.",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                fallback = Some(input.parse()?);
+            } else {
+                entries.push(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(Handlers {
+            event_cpi,
+            lazy,
+            markers,
+            mode,
+            entries,
+            fallback,
+        })
+    }
+}
+
+impl Handlers {
+    /// Discriminators of the entries, a discriminator equal to or prefixing another one is
+    /// rejected.
+    pub fn discriminators(&self) -> syn::Result<Vec<HandlerDiscriminator<'_>>> {
+        let mut discriminators: Vec<HandlerDiscriminator> = Vec::with_capacity(self.entries.len());
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let discriminator = match &entry.discriminator {
+                Some(expr) => match literal_bytes(expr, self.mode)? {
+                    Some(bytes) => HandlerDiscriminator::Bytes(bytes),
+                    None => HandlerDiscriminator::Expr(expr),
+                },
+                None => HandlerDiscriminator::Bytes(self.mode.discriminator(i, entry.name()?)),
+            };
+
+            if let HandlerDiscriminator::Bytes(ref bytes) = discriminator {
+                if bytes.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        entry.span(),
+                        "A discriminator cannot be empty.",
+                    ));
+                }
+
+                for (other_entry, other) in self.entries.iter().zip(&discriminators) {
+                    let HandlerDiscriminator::Bytes(other) = other else {
+                        continue;
+                    };
+
+                    let other_name = other_entry.path.to_token_stream();
+                    if bytes == other {
+                        return Err(syn::Error::new_spanned(
+                            entry.span(),
+                            format!("Duplicate discriminator, already used by `{other_name}`."),
+                        ));
+                    }
+
+                    if bytes.starts_with(other) || other.starts_with(bytes) {
+                        return Err(syn::Error::new_spanned(
+                            entry.span(),
+                            format!("The discriminator overlaps with the one of `{other_name}`."),
+                        ));
+                    }
+                }
+            }
+
+            discriminators.push(discriminator);
+        }
+
+        Ok(discriminators)
+    }
+}
+
+/// Bytes of a literal discriminator: `5`, `[1, 2, 3]` or `b"deposit"`. Integers are encoded as
+/// a `u16` in the `u16` mode and as a `u8` otherwise.
+fn literal_bytes(expr: &Expr, mode: DiscriminatorMode) -> syn::Result<Option<Vec<u8>>> {
+    match expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Int(lit) if mode == DiscriminatorMode::U16 => {
+                Ok(Some(lit.base10_parse::<u16>()?.to_le_bytes().to_vec()))
+            }
+            Lit::Int(lit) => Ok(Some(vec![lit.base10_parse()?])),
+            Lit::ByteStr(lit) => Ok(Some(lit.value())),
+            _ => Err(syn::Error::new_spanned(expr, "Invalid discriminator.")),
+        },
+        Expr::Array(array) => {
+            let mut bytes = Vec::with_capacity(array.elems.len());
+            for elem in &array.elems {
+                let Expr::Lit(expr_lit) = elem else {
+                    return Ok(None);
+                };
+                let Lit::Int(lit) = &expr_lit.lit else {
+                    return Err(syn::Error::new_spanned(elem, "Invalid discriminator byte."));
+                };
+                bytes.push(lit.base10_parse()?);
+            }
+
+            Ok(Some(bytes))
+        }
+        Expr::Reference(reference) => literal_bytes(&reference.expr, mode),
+        _ => Ok(None),
+    }
+}
+
+#[derive(Default)]
+pub struct InstructionsList(pub Vec<(Vec<u8>, Ident)>);
+
+impl TryFrom<&syn::ItemMacro> for InstructionsList {
+    type Error = syn::Error;
+
+    fn try_from(value: &syn::ItemMacro) -> syn::Result<Self> {
+        let handlers: Handlers = value.mac.parse_body()?;
+
+        // The clients and the IDL are generated from the source, the bytes of a handler routed
+        // with a constant are only known once the program is compiled.
+        let instructions = handlers
+            .entries
+            .iter()
+            .zip(handlers.discriminators()?)
+            .map(|(entry, discriminator)| {
+                let name = entry.name()?;
+                match discriminator {
+                    HandlerDiscriminator::Bytes(bytes) => Ok((bytes, name.clone())),
+                    HandlerDiscriminator::Expr(expr) => Err(syn::Error::new_spanned(
+                        expr,
+                        format!(
+                            "The discriminator of `{name}` must be a literal to generate the instruction clients and the IDL."
+                        ),
+                    )),
+                }
+            })
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self(instructions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, syn::parse_quote};
+
+    #[test]
+    fn test_instructions_list_with_attributes() {
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                #![event_cpi]
+                initialize,
+                increment,
+            }
+        };
+        let list = InstructionsList::try_from(&item).unwrap();
+
+        assert_eq!(list.0.len(), 2);
+        assert_eq!(list.0[1].0, [1]);
+        assert_eq!(list.0[1].1, "increment");
+    }
+
+    #[test]
+    fn test_instructions_list_anchor_discriminators() {
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                #![discriminator(anchor)]
+                initialize,
+                increment,
+            }
+        };
+        let list = InstructionsList::try_from(&item).unwrap();
+
+        assert_eq!(list.0[0].0, [175, 175, 109, 31, 13, 152, 155, 237]);
+        assert_eq!(list.0[1].0, sighash("global", "increment"));
+
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                #![discriminator(sighash)]
+                initialize,
+            }
+        };
+        assert!(InstructionsList::try_from(&item).is_err());
+    }
+
+    #[test]
+    fn test_instructions_list_explicit_discriminators() {
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                0 => initialize,
+                5 => deposit,
+                [6, 1] => withdraw,
+                b"close" => instructions::close,
+            }
+        };
+        let list = InstructionsList::try_from(&item).unwrap();
+
+        assert_eq!(list.0[0].0, [0]);
+        assert_eq!(list.0[1].0, [5]);
+        assert_eq!(list.0[2].0, [6, 1]);
+        assert_eq!(list.0[3].0, b"close");
+        assert_eq!(list.0[3].1, "close");
+
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                initialize,
+                0 => deposit,
+            }
+        };
+        assert!(InstructionsList::try_from(&item).is_err());
+
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                [1, 2] => deposit,
+                1 => withdraw,
+            }
+        };
+        assert!(InstructionsList::try_from(&item).is_err());
+
+        let item: syn::ItemMacro = parse_quote! {
+            handlers! {
+                initialize,
+                DEPOSIT_DISCRIMINATOR => deposit,
+            }
+        };
+        let err = InstructionsList::try_from(&item).err().unwrap();
+        assert!(err.to_string().contains("`deposit`"));
+    }
+
+    #[test]
+    fn test_handlers_u16_and_fallback() {
+        let handlers: Handlers = parse_quote! {
+            #![discriminator(u16)]
+            #![entrypoint(lazy)]
+            initialize,
+            300 => deposit,
+            fallback = process_foreign,
+        };
+        let discriminators = handlers.discriminators().unwrap();
+
+        assert!(handlers.lazy);
+        assert_eq!(handlers.entries.len(), 2);
+        assert!(matches!(&discriminators[0], HandlerDiscriminator::Bytes(b) if b == &[0, 0]));
+        assert!(matches!(&discriminators[1], HandlerDiscriminator::Bytes(b) if b == &[44, 1]));
+        assert!(handlers
+            .fallback
+            .is_some_and(|path| path.is_ident("process_foreign")));
+
+        assert!(syn::parse_str::<Handlers>("300 => deposit")
+            .unwrap()
+            .discriminators()
+            .is_err());
+        assert!(syn::parse_str::<Handlers>("fallback = a, fallback = b").is_err());
+        assert!(syn::parse_str::<Handlers>("default = a").is_err());
+        assert!(syn::parse_str::<Handlers>("#![entrypoint(eager)] a").is_err());
+        assert!(!handlers.markers);
+        assert!(syn::parse_str::<Handlers>("#![markers] a").unwrap().markers);
+        assert!(syn::parse_str::<Handlers>("#![markers(a)] a").is_err());
+    }
+}
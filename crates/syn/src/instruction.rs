@@ -2,7 +2,7 @@ use {
     crate::{helpers::PathHelper, Encoding},
     heck::ToSnakeCase,
     quote::format_ident,
-    syn::{parse::Parser, punctuated::Punctuated, FnArg, Ident, Pat, Type},
+    syn::{FnArg, Ident, Pat, Type},
 };
 
 pub struct InstructionReturnData {
@@ -112,25 +112,6 @@ fn extract_name(pat: &Pat) -> Option<Ident> {
     }
 }
 
-#[derive(Default)]
-pub struct InstructionsList(pub Vec<(usize, Ident)>);
-
-impl TryFrom<&syn::ItemMacro> for InstructionsList {
-    type Error = syn::Error;
-
-    fn try_from(value: &syn::ItemMacro) -> syn::Result<Self> {
-        let instructions = Punctuated::<Ident, syn::Token![,]>::parse_terminated
-            .parse2(value.mac.tokens.clone())?;
-        Ok(Self(
-            instructions
-                .iter()
-                .enumerate()
-                .map(|(i, n)| (i, n.clone()))
-                .collect(),
-        ))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use {
@@ -8,7 +8,10 @@ mod context;
 mod data;
 mod doc;
 mod errors;
+mod handlers;
 mod instruction;
 mod macros;
 
-pub use {account::*, arguments::*, context::*, data::*, doc::*, errors::*, instruction::*};
+pub use {
+    account::*, arguments::*, context::*, data::*, doc::*, errors::*, handlers::*, instruction::*,
+};
@@ -0,0 +1,90 @@
+use {
+    crate::{Mint, MAX_SIGNERS},
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{AccountMeta, Instruction, Signer as CpiSigner},
+        program::{invoke_signed, invoke_signed_with_bounds},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    typhoon_errors::Error,
+};
+
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MAX_MULTISIG_ACCOUNTS: usize = 4 + MAX_SIGNERS;
+
+/// Invokes a token instruction on `program_id`, the program owning the token accounts.
+#[inline(always)]
+pub(crate) fn invoke_token<const N: usize>(
+    program_id: &Pubkey,
+    accounts: &[&AccountInfo; N],
+    metas: &[AccountMeta; N],
+    data: &[u8],
+    signers: Option<&[CpiSigner]>,
+) -> Result<(), Error> {
+    invoke_signed(
+        &Instruction {
+            program_id,
+            accounts: metas,
+            data,
+        },
+        accounts,
+        signers.unwrap_or_default(),
+    )
+    .map_err(Into::into)
+}
+
+/// Invokes a token instruction whose authority is a multisig, `multisig_signers` are appended to
+/// the accounts as signers.
+#[inline(always)]
+pub(crate) fn invoke_token_multisig<const N: usize>(
+    program_id: &Pubkey,
+    accounts: &[&AccountInfo; N],
+    metas: &[AccountMeta; N],
+    multisig_signers: &[AccountInfo],
+    data: &[u8],
+    signers: Option<&[CpiSigner]>,
+) -> Result<(), Error> {
+    let len = N + multisig_signers.len();
+    if len > MAX_MULTISIG_ACCOUNTS {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
+    let mut infos = [accounts[0]; MAX_MULTISIG_ACCOUNTS];
+    let mut all_metas: [AccountMeta; MAX_MULTISIG_ACCOUNTS] =
+        core::array::from_fn(|_| metas[0].clone());
+    infos[..N].copy_from_slice(accounts);
+    all_metas[..N].clone_from_slice(metas);
+    for (i, signer) in multisig_signers.iter().enumerate() {
+        infos[N + i] = signer;
+        all_metas[N + i] = AccountMeta::readonly_signer(signer.key());
+    }
+
+    invoke_signed_with_bounds::<MAX_MULTISIG_ACCOUNTS>(
+        &Instruction {
+            program_id,
+            accounts: &all_metas[..len],
+            data,
+        },
+        &infos[..len],
+        signers.unwrap_or_default(),
+    )
+    .map_err(Into::into)
+}
+
+#[inline(always)]
+pub(crate) fn amount_data(discriminator: u8, amount: u64) -> [u8; 9] {
+    let mut data = [discriminator; 9];
+    data[1..].copy_from_slice(&amount.to_le_bytes());
+    data
+}
+
+#[inline(always)]
+pub(crate) fn mint_decimals(mint: &AccountInfo) -> Result<u8, Error> {
+    let data = mint.try_borrow_data()?;
+    if data.len() < Mint::LEN {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+
+    Ok(data[MINT_DECIMALS_OFFSET])
+}
@@ -0,0 +1,335 @@
+use {
+    super::{
+        ExtensionType, GroupMemberPointer, GroupPointer, InterestBearingConfig, MetadataPointer,
+        MintCloseAuthority, PausableConfig, PermanentDelegate, TransferFeeConfig, TransferHook,
+        ACCOUNT_TYPE_OFFSET, TLV_HEADER_LEN,
+    },
+    crate::Mint,
+    core::mem::size_of,
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{AccountMeta, Instruction, Signer as CpiSigner},
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+    pinocchio_token::state::AccountState,
+    typhoon_errors::Error,
+};
+
+const MULTISIG_LEN: usize = 355;
+const MAX_INSTRUCTION_DATA_LEN: usize = 78;
+
+/// Parameters of a mint extension initialized before `InitializeMint2`.
+#[derive(Clone, Copy)]
+pub enum MintExtension<'a> {
+    MintCloseAuthority {
+        close_authority: Option<&'a Pubkey>,
+    },
+    TransferFee {
+        config_authority: Option<&'a Pubkey>,
+        withdraw_withheld_authority: Option<&'a Pubkey>,
+        basis_points: u16,
+        maximum_fee: u64,
+    },
+    DefaultAccountState {
+        state: AccountState,
+    },
+    NonTransferable,
+    InterestBearing {
+        rate_authority: Option<&'a Pubkey>,
+        rate: i16,
+    },
+    PermanentDelegate {
+        delegate: &'a Pubkey,
+    },
+    TransferHook {
+        authority: Option<&'a Pubkey>,
+        program_id: Option<&'a Pubkey>,
+    },
+    MetadataPointer {
+        authority: Option<&'a Pubkey>,
+        metadata_address: Option<&'a Pubkey>,
+    },
+    GroupPointer {
+        authority: Option<&'a Pubkey>,
+        group_address: Option<&'a Pubkey>,
+    },
+    GroupMemberPointer {
+        authority: Option<&'a Pubkey>,
+        member_address: Option<&'a Pubkey>,
+    },
+    Pausable {
+        authority: &'a Pubkey,
+    },
+}
+
+impl MintExtension<'_> {
+    pub fn extension_type(&self) -> ExtensionType {
+        match self {
+            MintExtension::MintCloseAuthority { .. } => ExtensionType::MintCloseAuthority,
+            MintExtension::TransferFee { .. } => ExtensionType::TransferFeeConfig,
+            MintExtension::DefaultAccountState { .. } => ExtensionType::DefaultAccountState,
+            MintExtension::NonTransferable => ExtensionType::NonTransferable,
+            MintExtension::InterestBearing { .. } => ExtensionType::InterestBearingConfig,
+            MintExtension::PermanentDelegate { .. } => ExtensionType::PermanentDelegate,
+            MintExtension::TransferHook { .. } => ExtensionType::TransferHook,
+            MintExtension::MetadataPointer { .. } => ExtensionType::MetadataPointer,
+            MintExtension::GroupPointer { .. } => ExtensionType::GroupPointer,
+            MintExtension::GroupMemberPointer { .. } => ExtensionType::GroupMemberPointer,
+            MintExtension::Pausable { .. } => ExtensionType::Pausable,
+        }
+    }
+
+    /// Size of the extension value stored in the mint TLV data.
+    pub fn len(&self) -> usize {
+        match self {
+            MintExtension::MintCloseAuthority { .. } => size_of::<MintCloseAuthority>(),
+            MintExtension::TransferFee { .. } => size_of::<TransferFeeConfig>(),
+            MintExtension::DefaultAccountState { .. } => size_of::<super::DefaultAccountState>(),
+            MintExtension::NonTransferable => size_of::<super::NonTransferable>(),
+            MintExtension::InterestBearing { .. } => size_of::<InterestBearingConfig>(),
+            MintExtension::PermanentDelegate { .. } => size_of::<PermanentDelegate>(),
+            MintExtension::TransferHook { .. } => size_of::<TransferHook>(),
+            MintExtension::MetadataPointer { .. } => size_of::<MetadataPointer>(),
+            MintExtension::GroupPointer { .. } => size_of::<GroupPointer>(),
+            MintExtension::GroupMemberPointer { .. } => size_of::<GroupMemberPointer>(),
+            MintExtension::Pausable { .. } => size_of::<PausableConfig>(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn instruction_data(&self, data: &mut InstructionData) {
+        match self {
+            MintExtension::MintCloseAuthority { close_authority } => {
+                data.push(&[25]);
+                data.push_coption(*close_authority);
+            }
+            MintExtension::TransferFee {
+                config_authority,
+                withdraw_withheld_authority,
+                basis_points,
+                maximum_fee,
+            } => {
+                data.push(&[26, 0]);
+                data.push_coption(*config_authority);
+                data.push_coption(*withdraw_withheld_authority);
+                data.push(&basis_points.to_le_bytes());
+                data.push(&maximum_fee.to_le_bytes());
+            }
+            MintExtension::DefaultAccountState { state } => {
+                data.push(&[28, 0, *state as u8]);
+            }
+            MintExtension::NonTransferable => data.push(&[32]),
+            MintExtension::InterestBearing {
+                rate_authority,
+                rate,
+            } => {
+                data.push(&[33, 0]);
+                data.push_optional_nonzero(*rate_authority);
+                data.push(&rate.to_le_bytes());
+            }
+            MintExtension::PermanentDelegate { delegate } => {
+                data.push(&[35]);
+                data.push(delegate.as_ref());
+            }
+            MintExtension::TransferHook {
+                authority,
+                program_id,
+            } => {
+                data.push(&[36, 0]);
+                data.push_optional_nonzero(*authority);
+                data.push_optional_nonzero(*program_id);
+            }
+            MintExtension::MetadataPointer {
+                authority,
+                metadata_address,
+            } => {
+                data.push(&[39, 0]);
+                data.push_optional_nonzero(*authority);
+                data.push_optional_nonzero(*metadata_address);
+            }
+            MintExtension::GroupPointer {
+                authority,
+                group_address,
+            } => {
+                data.push(&[40, 0]);
+                data.push_optional_nonzero(*authority);
+                data.push_optional_nonzero(*group_address);
+            }
+            MintExtension::GroupMemberPointer {
+                authority,
+                member_address,
+            } => {
+                data.push(&[41, 0]);
+                data.push_optional_nonzero(*authority);
+                data.push_optional_nonzero(*member_address);
+            }
+            MintExtension::Pausable { authority } => {
+                data.push(&[44, 0]);
+                data.push(authority.as_ref());
+            }
+        }
+    }
+
+    /// Invokes the instruction initializing the extension, it must run before `InitializeMint2`.
+    pub fn initialize(&self, mint: &AccountInfo, token_program: &Pubkey) -> Result<(), Error> {
+        let mut data = InstructionData::default();
+        self.instruction_data(&mut data);
+
+        invoke_signed(
+            &Instruction {
+                program_id: token_program,
+                accounts: &[AccountMeta::writable(mint.key())],
+                data: data.as_slice(),
+            },
+            &[mint],
+            &[],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Account size of a mint initialized with `extensions`.
+pub fn mint_space(extensions: &[MintExtension]) -> usize {
+    if extensions.is_empty() {
+        return Mint::LEN;
+    }
+
+    let space = extensions.iter().fold(ACCOUNT_TYPE_OFFSET + 1, |acc, ext| {
+        acc + TLV_HEADER_LEN + ext.len()
+    });
+
+    // Token-2022 pads accounts that would be mistaken for a multisig.
+    if space == MULTISIG_LEN {
+        space + size_of::<ExtensionType>()
+    } else {
+        space
+    }
+}
+
+/// `InitializeMint2` targeting `token_program`.
+pub(crate) fn initialize_mint2(
+    mint: &AccountInfo,
+    token_program: &Pubkey,
+    mint_authority: &Pubkey,
+    decimals: u8,
+    freeze_authority: Option<&Pubkey>,
+    seeds: &[CpiSigner],
+) -> Result<(), Error> {
+    let mut data = InstructionData::default();
+    data.push(&[20, decimals]);
+    data.push(mint_authority.as_ref());
+    data.push_coption(freeze_authority);
+
+    invoke_signed(
+        &Instruction {
+            program_id: token_program,
+            accounts: &[AccountMeta::writable(mint.key())],
+            data: data.as_slice(),
+        },
+        &[mint],
+        seeds,
+    )?;
+
+    Ok(())
+}
+
+struct InstructionData {
+    buffer: [u8; MAX_INSTRUCTION_DATA_LEN],
+    len: usize,
+}
+
+impl Default for InstructionData {
+    fn default() -> Self {
+        InstructionData {
+            buffer: [0; MAX_INSTRUCTION_DATA_LEN],
+            len: 0,
+        }
+    }
+}
+
+impl InstructionData {
+    fn push(&mut self, bytes: &[u8]) {
+        self.buffer[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    fn push_coption(&mut self, value: Option<&Pubkey>) {
+        match value {
+            Some(key) => {
+                self.push(&[1]);
+                self.push(key.as_ref());
+            }
+            None => self.push(&[0]),
+        }
+    }
+
+    fn push_optional_nonzero(&mut self, value: Option<&Pubkey>) {
+        self.push(value.unwrap_or(&Pubkey::default()));
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mint_space_with_extensions() {
+        assert_eq!(mint_space(&[]), 82);
+        assert_eq!(mint_space(&[MintExtension::NonTransferable]), 170);
+
+        let key = Pubkey::from([1; 32]);
+        let extensions = [
+            MintExtension::MetadataPointer {
+                authority: Some(&key),
+                metadata_address: Some(&key),
+            },
+            MintExtension::TransferFee {
+                config_authority: None,
+                withdraw_withheld_authority: None,
+                basis_points: 50,
+                maximum_fee: 1_000,
+            },
+        ];
+        assert_eq!(mint_space(&extensions), 166 + 4 + 64 + 4 + 108);
+    }
+
+    #[test]
+    fn extension_instruction_data() {
+        let key = Pubkey::from([3; 32]);
+
+        let mut data = InstructionData::default();
+        MintExtension::TransferFee {
+            config_authority: Some(&key),
+            withdraw_withheld_authority: None,
+            basis_points: 250,
+            maximum_fee: 10,
+        }
+        .instruction_data(&mut data);
+        let bytes = data.as_slice();
+        assert_eq!(bytes.len(), 2 + 33 + 1 + 2 + 8);
+        assert_eq!(&bytes[..3], &[26, 0, 1]);
+        assert_eq!(&bytes[3..35], &key);
+        assert_eq!(bytes[35], 0);
+        assert_eq!(&bytes[36..38], &250u16.to_le_bytes());
+
+        let mut data = InstructionData::default();
+        MintExtension::MetadataPointer {
+            authority: None,
+            metadata_address: Some(&key),
+        }
+        .instruction_data(&mut data);
+        let bytes = data.as_slice();
+        assert_eq!(bytes.len(), 66);
+        assert_eq!(&bytes[2..34], &[0; 32]);
+        assert_eq!(&bytes[34..], &key);
+    }
+}
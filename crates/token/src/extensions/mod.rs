@@ -0,0 +1,339 @@
+use {
+    crate::{Mint, TokenAccount},
+    bytemuck::Pod,
+    pinocchio::account_info::Ref,
+    typhoon_accounts::{Discriminator, InterfaceAccount, Mut, ReadableAccount, RefFromBytes},
+    typhoon_errors::{Error, ErrorCode},
+};
+
+mod initialize;
+mod pod;
+mod transfer_fee;
+mod types;
+
+pub use {initialize::*, pod::*, transfer_fee::*, types::*};
+
+/// Size of a token account, mint extensions are padded to this size.
+pub const BASE_ACCOUNT_LEN: usize = TokenAccount::LEN;
+pub const ACCOUNT_TYPE_OFFSET: usize = BASE_ACCOUNT_LEN;
+pub const TLV_START: usize = ACCOUNT_TYPE_OFFSET + 1;
+pub const TLV_HEADER_LEN: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum ExtensionType {
+    Uninitialized,
+    TransferFeeConfig,
+    TransferFeeAmount,
+    MintCloseAuthority,
+    ConfidentialTransferMint,
+    ConfidentialTransferAccount,
+    DefaultAccountState,
+    ImmutableOwner,
+    MemoTransfer,
+    NonTransferable,
+    InterestBearingConfig,
+    CpiGuard,
+    PermanentDelegate,
+    NonTransferableAccount,
+    TransferHook,
+    TransferHookAccount,
+    ConfidentialTransferFeeConfig,
+    ConfidentialTransferFeeAmount,
+    MetadataPointer,
+    TokenMetadata,
+    GroupPointer,
+    TokenGroup,
+    GroupMemberPointer,
+    TokenGroupMember,
+    ConfidentialMintBurn,
+    ScaledUiAmount,
+    Pausable,
+    PausableAccount,
+}
+
+impl ExtensionType {
+    const ALL: [ExtensionType; 28] = [
+        ExtensionType::Uninitialized,
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::TransferFeeAmount,
+        ExtensionType::MintCloseAuthority,
+        ExtensionType::ConfidentialTransferMint,
+        ExtensionType::ConfidentialTransferAccount,
+        ExtensionType::DefaultAccountState,
+        ExtensionType::ImmutableOwner,
+        ExtensionType::MemoTransfer,
+        ExtensionType::NonTransferable,
+        ExtensionType::InterestBearingConfig,
+        ExtensionType::CpiGuard,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::NonTransferableAccount,
+        ExtensionType::TransferHook,
+        ExtensionType::TransferHookAccount,
+        ExtensionType::ConfidentialTransferFeeConfig,
+        ExtensionType::ConfidentialTransferFeeAmount,
+        ExtensionType::MetadataPointer,
+        ExtensionType::TokenMetadata,
+        ExtensionType::GroupPointer,
+        ExtensionType::TokenGroup,
+        ExtensionType::GroupMemberPointer,
+        ExtensionType::TokenGroupMember,
+        ExtensionType::ConfidentialMintBurn,
+        ExtensionType::ScaledUiAmount,
+        ExtensionType::Pausable,
+        ExtensionType::PausableAccount,
+    ];
+
+    #[inline(always)]
+    pub fn from_u16(value: u16) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
+/// Account type byte written after the base state of Token-2022 accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AccountType {
+    Uninitialized,
+    Mint,
+    Account,
+}
+
+/// Fixed size extension stored in the TLV data of a Token-2022 account.
+pub trait Extension: Pod {
+    const TYPE: ExtensionType;
+}
+
+/// Base state of a Token-2022 account that can carry extensions.
+pub trait BaseState {
+    const ACCOUNT_TYPE: AccountType;
+}
+
+impl BaseState for Mint {
+    const ACCOUNT_TYPE: AccountType = AccountType::Mint;
+}
+
+impl BaseState for TokenAccount {
+    const ACCOUNT_TYPE: AccountType = AccountType::Account;
+}
+
+/// Returns the TLV data of the account, empty for accounts without extensions.
+#[inline]
+pub fn tlv_data<S: BaseState>(data: &[u8]) -> &[u8] {
+    match data.get(ACCOUNT_TYPE_OFFSET) {
+        Some(account_type) if *account_type == S::ACCOUNT_TYPE as u8 => &data[TLV_START..],
+        _ => &[],
+    }
+}
+
+/// Zero-copy iterator over the `(type, value)` entries of TLV data.
+pub struct TlvIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TlvIter<'a> {
+    #[inline(always)]
+    pub fn new(tlv_data: &'a [u8]) -> Self {
+        TlvIter { data: tlv_data }
+    }
+}
+
+impl<'a> Iterator for TlvIter<'a> {
+    type Item = (u16, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = self.data.get(..TLV_HEADER_LEN)?;
+        let ty = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        if ty == ExtensionType::Uninitialized as u16 {
+            return None;
+        }
+
+        let value = self.data.get(TLV_HEADER_LEN..TLV_HEADER_LEN + len)?;
+        self.data = &self.data[TLV_HEADER_LEN + len..];
+
+        Some((ty, value))
+    }
+}
+
+/// Returns the raw value of the extension `ty` from the account data.
+#[inline]
+pub fn get_extension_bytes<S: BaseState>(data: &[u8], ty: ExtensionType) -> Option<&[u8]> {
+    TlvIter::new(tlv_data::<S>(data)).find_map(|(t, value)| (t == ty as u16).then_some(value))
+}
+
+#[inline]
+pub fn get_extension<S: BaseState, E: Extension>(data: &[u8]) -> Option<&E> {
+    get_extension_bytes::<S>(data, E::TYPE).and_then(|value| bytemuck::try_from_bytes(value).ok())
+}
+
+/// Iterates over the extension types of the account data, unknown types are skipped.
+#[inline]
+pub fn get_extension_types<S: BaseState>(data: &[u8]) -> impl Iterator<Item = ExtensionType> + '_ {
+    TlvIter::new(tlv_data::<S>(data)).filter_map(|(ty, _)| ExtensionType::from_u16(ty))
+}
+
+/// Typed access to the extensions of `Mint` and `TokenAccount` accounts.
+pub trait ExtensionsReader: ReadableAccount {
+    type State: BaseState;
+
+    #[inline]
+    fn extension<E: Extension>(&self) -> Result<Option<Ref<'_, E>>, Error> {
+        let data = self.as_ref().try_borrow_data()?;
+
+        Ok(Ref::filter_map(data, get_extension::<Self::State, E>).ok())
+    }
+
+    #[inline]
+    fn extension_bytes(&self, ty: ExtensionType) -> Result<Option<Ref<'_, [u8]>>, Error> {
+        let data = self.as_ref().try_borrow_data()?;
+
+        Ok(Ref::filter_map(data, |data| get_extension_bytes::<Self::State>(data, ty)).ok())
+    }
+
+    #[inline]
+    fn has_extension(&self, ty: ExtensionType) -> Result<bool, Error> {
+        let data = self.as_ref().try_borrow_data()?;
+
+        let found = get_extension_types::<Self::State>(&data).any(|t| t == ty);
+
+        Ok(found)
+    }
+
+    /// Fails with `ForbiddenTokenExtension` if the account carries one of `forbidden`.
+    #[inline]
+    fn require_no_extensions(&self, forbidden: &[ExtensionType]) -> Result<(), Error> {
+        let data = self.as_ref().try_borrow_data()?;
+
+        if get_extension_types::<Self::State>(&data).any(|ty| forbidden.contains(&ty)) {
+            return Err(ErrorCode::ForbiddenTokenExtension.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> ExtensionsReader for InterfaceAccount<'_, T>
+where
+    T: BaseState + Discriminator + RefFromBytes,
+{
+    type State = T;
+}
+
+impl<T> ExtensionsReader for Mut<T>
+where
+    T: ExtensionsReader,
+    Mut<T>: ReadableAccount,
+{
+    type State = T::State;
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, pinocchio::pubkey::Pubkey};
+
+    fn push_tlv(data: &mut [u8], offset: usize, ty: ExtensionType, value: &[u8]) -> usize {
+        data[offset..offset + 2].copy_from_slice(&(ty as u16).to_le_bytes());
+        data[offset + 2..offset + 4].copy_from_slice(&(value.len() as u16).to_le_bytes());
+        data[offset + 4..offset + 4 + value.len()].copy_from_slice(value);
+        offset + 4 + value.len()
+    }
+
+    #[test]
+    fn parse_mint_extensions() {
+        let mut data = [0u8; 512];
+        data[ACCOUNT_TYPE_OFFSET] = AccountType::Mint as u8;
+
+        let close_authority = MintCloseAuthority {
+            close_authority: OptionalNonZeroPubkey::from(Some(&Pubkey::from([7; 32]))),
+        };
+        let fee = TransferFee {
+            epoch: 10.into(),
+            maximum_fee: 5_000.into(),
+            transfer_fee_basis_points: 100.into(),
+        };
+        let fee_config = TransferFeeConfig {
+            transfer_fee_config_authority: OptionalNonZeroPubkey::from(None),
+            withdraw_withheld_authority: OptionalNonZeroPubkey::from(None),
+            withheld_amount: 0.into(),
+            older_transfer_fee: fee,
+            newer_transfer_fee: fee,
+        };
+
+        let offset = push_tlv(
+            &mut data,
+            TLV_START,
+            ExtensionType::MintCloseAuthority,
+            bytemuck::bytes_of(&close_authority),
+        );
+        push_tlv(
+            &mut data,
+            offset,
+            ExtensionType::TransferFeeConfig,
+            bytemuck::bytes_of(&fee_config),
+        );
+
+        let mut types = get_extension_types::<Mint>(&data);
+        assert_eq!(types.next(), Some(ExtensionType::MintCloseAuthority));
+        assert_eq!(types.next(), Some(ExtensionType::TransferFeeConfig));
+        assert_eq!(types.next(), None);
+
+        let parsed = get_extension::<Mint, MintCloseAuthority>(&data).unwrap();
+        assert_eq!(parsed.close_authority.get(), Some(&Pubkey::from([7; 32])));
+        let parsed = get_extension::<Mint, TransferFeeConfig>(&data).unwrap();
+        assert_eq!(parsed, &fee_config);
+        assert!(get_extension::<Mint, PermanentDelegate>(&data).is_none());
+
+        // The same bytes are not read as token account extensions.
+        assert!(get_extension::<TokenAccount, MintCloseAuthority>(&data).is_none());
+    }
+
+    #[test]
+    fn base_account_without_extensions() {
+        let data = [0u8; BASE_ACCOUNT_LEN];
+        assert_eq!(get_extension_types::<TokenAccount>(&data).count(), 0);
+    }
+
+    #[test]
+    fn truncated_tlv_entry() {
+        let mut data = [0u8; TLV_START + 8];
+        data[ACCOUNT_TYPE_OFFSET] = AccountType::Account as u8;
+        data[TLV_START..TLV_START + 2]
+            .copy_from_slice(&(ExtensionType::TransferFeeAmount as u16).to_le_bytes());
+        data[TLV_START + 2..TLV_START + 4].copy_from_slice(&8u16.to_le_bytes());
+
+        assert!(get_extension::<TokenAccount, TransferFeeAmount>(&data).is_none());
+    }
+
+    #[test]
+    fn transfer_fee_calculation() {
+        let fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: 50.into(),
+            transfer_fee_basis_points: 250.into(),
+        };
+
+        assert_eq!(fee.calculate_fee(0), Some(0));
+        assert_eq!(fee.calculate_fee(1), Some(1));
+        assert_eq!(fee.calculate_fee(1_000), Some(25));
+        assert_eq!(fee.calculate_fee(1_000_000), Some(50));
+        assert_eq!(fee.calculate_post_fee_amount(1_000), Some(975));
+
+        let newer = TransferFee {
+            epoch: 5.into(),
+            maximum_fee: u64::MAX.into(),
+            transfer_fee_basis_points: MAX_FEE_BASIS_POINTS.into(),
+        };
+        let config = TransferFeeConfig {
+            transfer_fee_config_authority: OptionalNonZeroPubkey::from(None),
+            withdraw_withheld_authority: OptionalNonZeroPubkey::from(None),
+            withheld_amount: 0.into(),
+            older_transfer_fee: fee,
+            newer_transfer_fee: newer,
+        };
+
+        assert_eq!(config.calculate_epoch_fee(4, 1_000), Some(25));
+        assert_eq!(config.calculate_epoch_fee(5, 1_000), Some(1_000));
+        assert_eq!(config.calculate_post_fee_amount(5, 1_000), Some(0));
+    }
+}
@@ -0,0 +1,75 @@
+use {
+    bytemuck::{Pod, Zeroable},
+    pinocchio::pubkey::Pubkey,
+};
+
+macro_rules! pod_int {
+    ($name:ident, $ty:ty, $size:literal) => {
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+        #[repr(transparent)]
+        pub struct $name(pub [u8; $size]);
+
+        impl From<$ty> for $name {
+            #[inline(always)]
+            fn from(value: $ty) -> Self {
+                $name(value.to_le_bytes())
+            }
+        }
+
+        impl From<$name> for $ty {
+            #[inline(always)]
+            fn from(value: $name) -> Self {
+                <$ty>::from_le_bytes(value.0)
+            }
+        }
+
+        impl $name {
+            #[inline(always)]
+            pub fn get(&self) -> $ty {
+                <$ty>::from_le_bytes(self.0)
+            }
+        }
+    };
+}
+
+pod_int!(PodU16, u16, 2);
+pod_int!(PodI16, i16, 2);
+pod_int!(PodU64, u64, 8);
+pod_int!(PodI64, i64, 8);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodBool(pub u8);
+
+impl PodBool {
+    #[inline(always)]
+    pub fn get(&self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl From<bool> for PodBool {
+    #[inline(always)]
+    fn from(value: bool) -> Self {
+        PodBool(value as u8)
+    }
+}
+
+/// A pubkey where the zero value means `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct OptionalNonZeroPubkey(pub Pubkey);
+
+impl OptionalNonZeroPubkey {
+    #[inline(always)]
+    pub fn get(&self) -> Option<&Pubkey> {
+        (self.0 != Pubkey::default()).then_some(&self.0)
+    }
+}
+
+impl From<Option<&Pubkey>> for OptionalNonZeroPubkey {
+    #[inline(always)]
+    fn from(value: Option<&Pubkey>) -> Self {
+        OptionalNonZeroPubkey(value.copied().unwrap_or_default())
+    }
+}
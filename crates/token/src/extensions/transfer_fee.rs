@@ -0,0 +1,80 @@
+use {
+    super::{Extension, ExtensionType, OptionalNonZeroPubkey, PodU16, PodU64},
+    bytemuck::{Pod, Zeroable},
+};
+
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct TransferFee {
+    pub epoch: PodU64,
+    pub maximum_fee: PodU64,
+    pub transfer_fee_basis_points: PodU16,
+}
+
+impl TransferFee {
+    /// Fee charged on a transfer of `pre_fee_amount`, rounded up.
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+        let basis_points = self.transfer_fee_basis_points.get() as u128;
+        if basis_points == 0 || pre_fee_amount == 0 {
+            return Some(0);
+        }
+
+        let numerator = (pre_fee_amount as u128).checked_mul(basis_points)?;
+        let fee = numerator
+            .checked_add(MAX_FEE_BASIS_POINTS as u128 - 1)?
+            .checked_div(MAX_FEE_BASIS_POINTS as u128)?;
+
+        Some((fee as u64).min(self.maximum_fee.get()))
+    }
+
+    /// Amount received by the destination of a transfer of `pre_fee_amount`.
+    pub fn calculate_post_fee_amount(&self, pre_fee_amount: u64) -> Option<u64> {
+        pre_fee_amount.checked_sub(self.calculate_fee(pre_fee_amount)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct TransferFeeConfig {
+    pub transfer_fee_config_authority: OptionalNonZeroPubkey,
+    pub withdraw_withheld_authority: OptionalNonZeroPubkey,
+    pub withheld_amount: PodU64,
+    pub older_transfer_fee: TransferFee,
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Fee schedule in effect at `epoch`.
+    pub fn get_epoch_fee(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch.get() {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    pub fn calculate_epoch_fee(&self, epoch: u64, pre_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch).calculate_fee(pre_fee_amount)
+    }
+
+    pub fn calculate_post_fee_amount(&self, epoch: u64, pre_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch)
+            .calculate_post_fee_amount(pre_fee_amount)
+    }
+}
+
+impl Extension for TransferFeeConfig {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeConfig;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct TransferFeeAmount {
+    pub withheld_amount: PodU64,
+}
+
+impl Extension for TransferFeeAmount {
+    const TYPE: ExtensionType = ExtensionType::TransferFeeAmount;
+}
@@ -0,0 +1,128 @@
+use {
+    super::{Extension, ExtensionType, OptionalNonZeroPubkey, PodBool, PodI16, PodI64},
+    bytemuck::{Pod, Zeroable},
+};
+
+macro_rules! extension {
+    ($name:ident) => {
+        impl Extension for $name {
+            const TYPE: ExtensionType = ExtensionType::$name;
+        }
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MintCloseAuthority {
+    pub close_authority: OptionalNonZeroPubkey,
+}
+extension!(MintCloseAuthority);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct DefaultAccountState {
+    pub state: u8,
+}
+extension!(DefaultAccountState);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ImmutableOwner;
+extension!(ImmutableOwner);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MemoTransfer {
+    pub require_incoming_transfer_memos: PodBool,
+}
+extension!(MemoTransfer);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct NonTransferable;
+extension!(NonTransferable);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct InterestBearingConfig {
+    pub rate_authority: OptionalNonZeroPubkey,
+    pub initialization_timestamp: PodI64,
+    pub pre_update_average_rate: PodI16,
+    pub last_update_timestamp: PodI64,
+    pub current_rate: PodI16,
+}
+extension!(InterestBearingConfig);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct CpiGuard {
+    pub lock_cpi: PodBool,
+}
+extension!(CpiGuard);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PermanentDelegate {
+    pub delegate: OptionalNonZeroPubkey,
+}
+extension!(PermanentDelegate);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct NonTransferableAccount;
+extension!(NonTransferableAccount);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct TransferHook {
+    pub authority: OptionalNonZeroPubkey,
+    pub program_id: OptionalNonZeroPubkey,
+}
+extension!(TransferHook);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct TransferHookAccount {
+    pub transferring: PodBool,
+}
+extension!(TransferHookAccount);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MetadataPointer {
+    pub authority: OptionalNonZeroPubkey,
+    pub metadata_address: OptionalNonZeroPubkey,
+}
+extension!(MetadataPointer);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct GroupPointer {
+    pub authority: OptionalNonZeroPubkey,
+    pub group_address: OptionalNonZeroPubkey,
+}
+extension!(GroupPointer);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct GroupMemberPointer {
+    pub authority: OptionalNonZeroPubkey,
+    pub member_address: OptionalNonZeroPubkey,
+}
+extension!(GroupMemberPointer);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PausableConfig {
+    pub authority: OptionalNonZeroPubkey,
+    pub paused: PodBool,
+}
+
+impl Extension for PausableConfig {
+    const TYPE: ExtensionType = ExtensionType::Pausable;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PausableAccount;
+extension!(PausableAccount);
@@ -9,17 +9,25 @@ use {
         state::{Mint as SplMint, TokenAccount as SplTokenAccount},
         ID as TOKEN_PROGRAM_ID,
     },
-    typhoon_accounts::{Discriminator, Owner, Owners, ProgramId, ProgramIds, RefFromBytes},
+    typhoon_accounts::{
+        Discriminator, Owner, Owners, ProgramId, ProgramIds, ReadableAccount, RefFromBytes,
+    },
 };
 
+mod cpi;
+mod multisig;
+mod native;
 mod traits;
 
+pub mod extensions;
+pub mod transfer_hook;
+
 pub use {
-    pinocchio_associated_token_account::instructions as ata_instructions,
+    multisig::*, native::*, pinocchio_associated_token_account::instructions as ata_instructions,
     pinocchio_token::instructions as spl_instructions, traits::*,
 };
 
-const TOKEN_2022_PROGRAM_ID: Pubkey =
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
     decode_32_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
 pub struct AtaTokenProgram;
@@ -80,6 +88,11 @@ pub struct TokenAccount(SplTokenAccount);
 
 impl TokenAccount {
     pub const LEN: usize = SplTokenAccount::LEN;
+
+    #[inline(always)]
+    pub fn is_native_mint(&self) -> bool {
+        self.mint() == &NATIVE_MINT
+    }
 }
 
 impl RefFromBytes for TokenAccount {
@@ -117,9 +130,25 @@ impl Deref for TokenAccount {
 }
 
 pub fn find_associated_token_address(mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    find_associated_token_address_with_program_id(mint, owner, &TOKEN_PROGRAM_ID)
+}
+
+pub fn find_associated_token_address_with_program_id(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
     find_program_address(
-        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
         &ATA_PROGRAM_ID,
     )
     .0
 }
+
+/// Derives the associated token address with the token program owning `mint`.
+pub fn find_associated_token_address_for_mint(
+    mint: &impl ReadableAccount,
+    owner: &Pubkey,
+) -> Pubkey {
+    find_associated_token_address_with_program_id(mint.key(), owner, mint.as_ref().owner())
+}
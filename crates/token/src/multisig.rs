@@ -0,0 +1,131 @@
+use {
+    crate::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    pinocchio::{account_info::AccountInfo, pubkey::Pubkey},
+    typhoon_accounts::{Discriminator, Owner, Owners, RefFromBytes},
+};
+
+/// Maximum number of signers of a multisig.
+pub const MAX_SIGNERS: usize = 11;
+
+/// SPL Token multisig account, an `m` of `n` authority over mints and token accounts.
+#[repr(C)]
+pub struct Multisig {
+    m: u8,
+    n: u8,
+    is_initialized: u8,
+    signers: [Pubkey; MAX_SIGNERS],
+}
+
+impl Multisig {
+    pub const LEN: usize = core::mem::size_of::<Multisig>();
+
+    /// Number of signers required.
+    #[inline(always)]
+    pub fn m(&self) -> u8 {
+        self.m
+    }
+
+    /// Number of valid signers.
+    #[inline(always)]
+    pub fn n(&self) -> u8 {
+        self.n
+    }
+
+    #[inline(always)]
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized != 0
+    }
+
+    #[inline(always)]
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..(self.n as usize).min(MAX_SIGNERS)]
+    }
+
+    /// Counts the multisig signers that signed among `accounts`, each signer slot is matched once
+    /// like the token program does.
+    pub fn count_signers(&self, accounts: &[AccountInfo]) -> usize {
+        let mut matched = [false; MAX_SIGNERS];
+        let mut count = 0;
+
+        for account in accounts.iter().filter(|account| account.is_signer()) {
+            for (signer, matched) in self.signers().iter().zip(matched.iter_mut()) {
+                if !*matched && signer == account.key() {
+                    *matched = true;
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Whether at least `m` multisig signers signed among `accounts`.
+    #[inline]
+    pub fn is_signed_by(&self, accounts: &[AccountInfo]) -> bool {
+        self.is_initialized() && self.count_signers(accounts) >= self.m as usize
+    }
+}
+
+impl RefFromBytes for Multisig {
+    fn read(data: &[u8]) -> Option<&Self> {
+        if data.len() != Self::LEN {
+            return None;
+        }
+
+        // SAFETY: `Multisig` only contains bytes and the length was checked.
+        Some(unsafe { &*(data.as_ptr() as *const Multisig) })
+    }
+
+    fn read_mut(data: &mut [u8]) -> Option<&mut Self> {
+        if data.len() != Self::LEN {
+            return None;
+        }
+
+        // SAFETY: `Multisig` only contains bytes and the length was checked.
+        Some(unsafe { &mut *(data.as_mut_ptr() as *mut Multisig) })
+    }
+}
+
+impl Discriminator for Multisig {
+    const DISCRIMINATOR: &'static [u8] = &[];
+}
+
+impl Owner for Multisig {
+    const OWNER: Pubkey = TOKEN_PROGRAM_ID;
+}
+
+impl Owners for Multisig {
+    const OWNERS: &'static [Pubkey] = &[TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_multisig() {
+        let mut data = [0; Multisig::LEN];
+        data[..3].copy_from_slice(&[2, 3, 1]);
+        for (i, signer) in data[3..].chunks_exact_mut(32).enumerate() {
+            signer.fill(i as u8 + 1);
+        }
+
+        assert_eq!(Multisig::LEN, 355);
+        let Some(multisig) = Multisig::read(&data) else {
+            panic!("multisig should be readable");
+        };
+        assert_eq!(multisig.m(), 2);
+        assert!(multisig.is_initialized());
+        assert_eq!(
+            multisig.signers(),
+            &[
+                Pubkey::from([1; 32]),
+                Pubkey::from([2; 32]),
+                Pubkey::from([3; 32])
+            ]
+        );
+        assert!(!multisig.is_signed_by(&[]));
+
+        assert!(Multisig::read(&data[..Multisig::LEN - 1]).is_none());
+    }
+}
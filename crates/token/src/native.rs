@@ -0,0 +1,33 @@
+use {
+    crate::TokenAccountCpi,
+    five8_const::decode_32_const,
+    pinocchio::{instruction::Signer as CpiSigner, pubkey::Pubkey},
+    typhoon_accounts::{ReadableAccount, WritableAccount},
+    typhoon_errors::Error,
+    typhoon_utility_traits::SystemCpi,
+};
+
+/// Mint of the wrapped SOL token accounts.
+pub const NATIVE_MINT: Pubkey = decode_32_const("So11111111111111111111111111111111111111112");
+
+/// Transfers `amount` lamports from `from` to the wrapped SOL account and syncs its token amount.
+#[inline(always)]
+pub fn wrap_sol<'a>(
+    from: &impl SystemCpi<'a>,
+    ata: &impl TokenAccountCpi,
+    amount: u64,
+) -> Result<(), Error> {
+    SystemCpi::transfer(from, ata, amount)?;
+    ata.sync_native()
+}
+
+/// Closes the wrapped SOL account owned by `authority`, the lamports are sent to `destination`.
+#[inline(always)]
+pub fn unwrap_sol(
+    ata: &impl TokenAccountCpi,
+    destination: &impl WritableAccount,
+    authority: &impl ReadableAccount,
+    signers: Option<&[CpiSigner]>,
+) -> Result<(), Error> {
+    ata.close_account(destination, authority, signers)
+}
@@ -1,5 +1,8 @@
 use {
-    crate::{Mint, TokenProgram},
+    crate::{
+        extensions::{initialize_mint2, mint_space, MintExtension},
+        Mint, TokenProgram,
+    },
     pinocchio::{
         account_info::AccountInfo, instruction::Signer as CpiSigner, pubkey::Pubkey,
         sysvars::rent::Rent,
@@ -41,6 +44,46 @@ where
 
         Mut::try_from_info(info)
     }
+
+    /// Creates a mint owned by `token_program`, initializing `extensions` before `InitializeMint2`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    fn create_mint_with_extensions(
+        self,
+        rent: &Rent,
+        payer: &impl WritableAccount,
+        token_program: &Pubkey,
+        mint_authority: &Pubkey,
+        decimals: u8,
+        freeze_authority: Option<&Pubkey>,
+        extensions: &[MintExtension],
+        seeds: Option<&[CpiSigner]>,
+    ) -> Result<Mut<T>, Error> {
+        let info = self.into();
+        create_or_assign(
+            info,
+            rent,
+            payer,
+            token_program,
+            mint_space(extensions),
+            seeds,
+        )?;
+
+        for extension in extensions {
+            extension.initialize(info, token_program)?;
+        }
+
+        initialize_mint2(
+            info,
+            token_program,
+            mint_authority,
+            decimals,
+            freeze_authority,
+            seeds.unwrap_or_default(),
+        )?;
+
+        Mut::try_from_info(info)
+    }
 }
 
 macro_rules! impl_trait {
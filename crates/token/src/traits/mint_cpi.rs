@@ -0,0 +1,104 @@
+use {
+    super::token_account_cpi::set_authority,
+    crate::{
+        cpi::{amount_data, invoke_token, invoke_token_multisig},
+        Mint,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{AccountMeta, Signer as CpiSigner},
+        pubkey::Pubkey,
+    },
+    pinocchio_token::instructions::AuthorityType,
+    typhoon_accounts::{Account, InterfaceAccount, Mut, ReadableAccount, WritableAccount},
+    typhoon_errors::Error,
+};
+
+/// Token instructions on a mint, invoked on the token program owning it.
+pub trait MintCpi: WritableAccount {
+    #[inline(always)]
+    fn mint_to(
+        &self,
+        to: &impl WritableAccount,
+        authority: &impl ReadableAccount,
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let mint = self.as_ref();
+        invoke_token(
+            mint.owner(),
+            &[mint, to.as_ref(), authority.as_ref()],
+            &[
+                AccountMeta::writable(mint.key()),
+                AccountMeta::writable(to.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &amount_data(7, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn set_authority(
+        &self,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+        authority: &impl ReadableAccount,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        set_authority(
+            self.as_ref(),
+            authority_type,
+            new_authority,
+            authority.as_ref(),
+            &[],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn mint_to_multisig(
+        &self,
+        to: &impl WritableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let mint = self.as_ref();
+        invoke_token_multisig(
+            mint.owner(),
+            &[mint, to.as_ref(), multisig.as_ref()],
+            &[
+                AccountMeta::writable(mint.key()),
+                AccountMeta::writable(to.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &amount_data(7, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn set_authority_multisig(
+        &self,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        set_authority(
+            self.as_ref(),
+            authority_type,
+            new_authority,
+            multisig.as_ref(),
+            multisig_signers,
+            signers,
+        )
+    }
+}
+
+impl MintCpi for Mut<Account<'_, Mint>> {}
+impl MintCpi for Mut<InterfaceAccount<'_, Mint>> {}
@@ -1,4 +1,6 @@
 mod create_account;
 mod create_mint;
+mod mint_cpi;
+mod token_account_cpi;
 
-pub use {create_account::*, create_mint::*};
+pub use {create_account::*, create_mint::*, mint_cpi::*, token_account_cpi::*};
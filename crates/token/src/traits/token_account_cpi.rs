@@ -0,0 +1,497 @@
+use {
+    crate::{
+        cpi::{amount_data, invoke_token, invoke_token_multisig, mint_decimals},
+        TokenAccount,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{AccountMeta, Signer as CpiSigner},
+        pubkey::Pubkey,
+    },
+    pinocchio_token::instructions::AuthorityType,
+    typhoon_accounts::{Account, InterfaceAccount, Mut, ReadableAccount, WritableAccount},
+    typhoon_errors::Error,
+};
+
+/// Token instructions on a token account, invoked on the token program owning it.
+pub trait TokenAccountCpi: WritableAccount {
+    #[inline(always)]
+    fn transfer(
+        &self,
+        to: &impl WritableAccount,
+        authority: &impl ReadableAccount,
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token(
+            account.owner(),
+            &[account, to.as_ref(), authority.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(to.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &amount_data(3, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn transfer_checked(
+        &self,
+        mint: &impl ReadableAccount,
+        to: &impl WritableAccount,
+        authority: &impl ReadableAccount,
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        let mut data = [0; 10];
+        data[..9].copy_from_slice(&amount_data(12, amount));
+        data[9] = mint_decimals(mint.as_ref())?;
+
+        invoke_token(
+            account.owner(),
+            &[account, mint.as_ref(), to.as_ref(), authority.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::readonly(mint.key()),
+                AccountMeta::writable(to.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &data,
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn burn(
+        &self,
+        mint: &impl WritableAccount,
+        authority: &impl ReadableAccount,
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token(
+            account.owner(),
+            &[account, mint.as_ref(), authority.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(mint.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &amount_data(8, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn approve(
+        &self,
+        delegate: &impl ReadableAccount,
+        authority: &impl ReadableAccount,
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token(
+            account.owner(),
+            &[account, delegate.as_ref(), authority.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::readonly(delegate.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &amount_data(4, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn revoke(
+        &self,
+        authority: &impl ReadableAccount,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token(
+            account.owner(),
+            &[account, authority.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &[5],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn set_authority(
+        &self,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+        authority: &impl ReadableAccount,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        set_authority(
+            self.as_ref(),
+            authority_type,
+            new_authority,
+            authority.as_ref(),
+            &[],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn freeze(
+        &self,
+        mint: &impl ReadableAccount,
+        freeze_authority: &impl ReadableAccount,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        freeze_or_thaw(
+            10,
+            self.as_ref(),
+            mint.as_ref(),
+            freeze_authority.as_ref(),
+            &[],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn thaw(
+        &self,
+        mint: &impl ReadableAccount,
+        freeze_authority: &impl ReadableAccount,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        freeze_or_thaw(
+            11,
+            self.as_ref(),
+            mint.as_ref(),
+            freeze_authority.as_ref(),
+            &[],
+            signers,
+        )
+    }
+
+    /// Updates the token amount of a wrapped SOL account to its lamports minus the rent.
+    #[inline(always)]
+    fn sync_native(&self) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token(
+            account.owner(),
+            &[account],
+            &[AccountMeta::writable(account.key())],
+            &[17],
+            None,
+        )
+    }
+
+    #[inline(always)]
+    fn close_account(
+        &self,
+        destination: &impl WritableAccount,
+        authority: &impl ReadableAccount,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token(
+            account.owner(),
+            &[account, destination.as_ref(), authority.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(destination.key()),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+            &[9],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn transfer_multisig(
+        &self,
+        to: &impl WritableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token_multisig(
+            account.owner(),
+            &[account, to.as_ref(), multisig.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(to.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &amount_data(3, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn transfer_checked_multisig(
+        &self,
+        mint: &impl ReadableAccount,
+        to: &impl WritableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        let mut data = [0; 10];
+        data[..9].copy_from_slice(&amount_data(12, amount));
+        data[9] = mint_decimals(mint.as_ref())?;
+
+        invoke_token_multisig(
+            account.owner(),
+            &[account, mint.as_ref(), to.as_ref(), multisig.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::readonly(mint.key()),
+                AccountMeta::writable(to.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &data,
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn burn_multisig(
+        &self,
+        mint: &impl WritableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token_multisig(
+            account.owner(),
+            &[account, mint.as_ref(), multisig.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(mint.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &amount_data(8, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn approve_multisig(
+        &self,
+        delegate: &impl ReadableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        amount: u64,
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token_multisig(
+            account.owner(),
+            &[account, delegate.as_ref(), multisig.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::readonly(delegate.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &amount_data(4, amount),
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn revoke_multisig(
+        &self,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token_multisig(
+            account.owner(),
+            &[account, multisig.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &[5],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn set_authority_multisig(
+        &self,
+        authority_type: AuthorityType,
+        new_authority: Option<&Pubkey>,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        set_authority(
+            self.as_ref(),
+            authority_type,
+            new_authority,
+            multisig.as_ref(),
+            multisig_signers,
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn freeze_multisig(
+        &self,
+        mint: &impl ReadableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        freeze_or_thaw(
+            10,
+            self.as_ref(),
+            mint.as_ref(),
+            multisig.as_ref(),
+            multisig_signers,
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn thaw_multisig(
+        &self,
+        mint: &impl ReadableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        freeze_or_thaw(
+            11,
+            self.as_ref(),
+            mint.as_ref(),
+            multisig.as_ref(),
+            multisig_signers,
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    fn close_account_multisig(
+        &self,
+        destination: &impl WritableAccount,
+        multisig: &impl ReadableAccount,
+        multisig_signers: &[AccountInfo],
+        signers: Option<&[CpiSigner]>,
+    ) -> Result<(), Error> {
+        let account = self.as_ref();
+        invoke_token_multisig(
+            account.owner(),
+            &[account, destination.as_ref(), multisig.as_ref()],
+            &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(destination.key()),
+                AccountMeta::readonly(multisig.key()),
+            ],
+            multisig_signers,
+            &[9],
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+fn freeze_or_thaw(
+    discriminator: u8,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    freeze_authority: &AccountInfo,
+    multisig_signers: &[AccountInfo],
+    signers: Option<&[CpiSigner]>,
+) -> Result<(), Error> {
+    let accounts = [account, mint, freeze_authority];
+    let metas = [
+        AccountMeta::writable(account.key()),
+        AccountMeta::readonly(mint.key()),
+        AccountMeta::new(freeze_authority.key(), false, multisig_signers.is_empty()),
+    ];
+
+    if multisig_signers.is_empty() {
+        invoke_token(
+            account.owner(),
+            &accounts,
+            &metas,
+            &[discriminator],
+            signers,
+        )
+    } else {
+        invoke_token_multisig(
+            account.owner(),
+            &accounts,
+            &metas,
+            multisig_signers,
+            &[discriminator],
+            signers,
+        )
+    }
+}
+
+#[inline(always)]
+pub(crate) fn set_authority(
+    account: &AccountInfo,
+    authority_type: AuthorityType,
+    new_authority: Option<&Pubkey>,
+    authority: &AccountInfo,
+    multisig_signers: &[AccountInfo],
+    signers: Option<&[CpiSigner]>,
+) -> Result<(), Error> {
+    let mut data = [0; 35];
+    data[0] = 6;
+    data[1] = authority_type as u8;
+    let len = match new_authority {
+        Some(new_authority) => {
+            data[2] = 1;
+            data[3..].copy_from_slice(new_authority);
+            35
+        }
+        None => 3,
+    };
+
+    let accounts = [account, authority];
+    let metas = [
+        AccountMeta::writable(account.key()),
+        AccountMeta::new(authority.key(), false, multisig_signers.is_empty()),
+    ];
+
+    if multisig_signers.is_empty() {
+        invoke_token(account.owner(), &accounts, &metas, &data[..len], signers)
+    } else {
+        invoke_token_multisig(
+            account.owner(),
+            &accounts,
+            &metas,
+            multisig_signers,
+            &data[..len],
+            signers,
+        )
+    }
+}
+
+impl TokenAccountCpi for Mut<Account<'_, TokenAccount>> {}
+impl TokenAccountCpi for Mut<InterfaceAccount<'_, TokenAccount>> {}
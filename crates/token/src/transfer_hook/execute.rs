@@ -0,0 +1,76 @@
+use {
+    super::{find_extra_account_metas_address, ExtraAccountMetaList, EXECUTE_DISCRIMINATOR},
+    core::mem::size_of,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    typhoon_context::HandlerContext,
+    typhoon_errors::{Error, ErrorCode},
+};
+
+const EXECUTE_ACCOUNTS_LEN: usize = 5;
+const EXECUTE_DATA_LEN: usize = EXECUTE_DISCRIMINATOR.len() + size_of::<u64>();
+
+/// Accounts and amount of the transfer hook `Execute` instruction, the extra accounts are checked
+/// against the metas stored in the validation account.
+pub struct Execute<'a> {
+    pub source: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub owner: &'a AccountInfo,
+    pub extra_account_metas: &'a AccountInfo,
+    pub extra_accounts: &'a [AccountInfo],
+    pub amount: u64,
+}
+
+impl<'b> HandlerContext<'_, 'b, '_> for Execute<'b> {
+    fn from_entrypoint(
+        program_id: &Pubkey,
+        accounts: &mut &'b [AccountInfo],
+        instruction_data: &mut &[u8],
+    ) -> Result<Self, Error> {
+        let [source, mint, destination, owner, extra_account_metas, ..] = *accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys.into());
+        };
+
+        let (amount, remaining) = instruction_data
+            .split_first_chunk::<{ size_of::<u64>() }>()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        if extra_account_metas.key() != &find_extra_account_metas_address(mint.key(), program_id).0
+        {
+            return Err(ProgramError::InvalidSeeds.into());
+        }
+
+        let mut data = [0; EXECUTE_DATA_LEN];
+        data[..EXECUTE_DISCRIMINATOR.len()].copy_from_slice(&EXECUTE_DISCRIMINATOR);
+        data[EXECUTE_DISCRIMINATOR.len()..].copy_from_slice(amount);
+
+        let metas_data = extra_account_metas.try_borrow_data()?;
+        let metas = ExtraAccountMetaList::unpack(&metas_data)?;
+        let len = EXECUTE_ACCOUNTS_LEN + metas.len();
+        if accounts.len() < len {
+            return Err(ProgramError::NotEnoughAccountKeys.into());
+        }
+
+        for (i, meta) in metas.iter().enumerate() {
+            let index = EXECUTE_ACCOUNTS_LEN + i;
+            let key = meta.resolve(&data, &accounts[..index], program_id)?;
+            if accounts[index].key() != &key {
+                return Err(ErrorCode::InvalidExtraAccount.into());
+            }
+        }
+
+        let (execute_accounts, rest) = accounts.split_at(len);
+        *accounts = rest;
+        *instruction_data = remaining;
+
+        Ok(Execute {
+            source,
+            mint,
+            destination,
+            owner,
+            extra_account_metas,
+            extra_accounts: &execute_accounts[EXECUTE_ACCOUNTS_LEN..],
+            amount: u64::from_le_bytes(*amount),
+        })
+    }
+}
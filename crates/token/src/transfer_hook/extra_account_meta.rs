@@ -0,0 +1,324 @@
+use {
+    crate::extensions::PodBool,
+    bytemuck::{Pod, Zeroable},
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::{find_program_address, Pubkey, MAX_SEEDS, MAX_SEED_LEN},
+    },
+    typhoon_errors::Error,
+};
+
+pub const ADDRESS_CONFIG_LEN: usize = 32;
+
+const PUBKEY_DISCRIMINATOR: u8 = 0;
+const PDA_DISCRIMINATOR: u8 = 1;
+const PUBKEY_DATA_DISCRIMINATOR: u8 = 2;
+const EXTERNAL_PDA_DISCRIMINATOR: u8 = 1 << 7;
+
+/// Seed of an extra account PDA, resolved when the hook is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seed<'a> {
+    Literal(&'a [u8]),
+    /// Bytes of the `Execute` instruction data, discriminator included.
+    InstructionData {
+        index: u8,
+        length: u8,
+    },
+    /// Key of the account at `index` in the `Execute` accounts.
+    AccountKey {
+        index: u8,
+    },
+    AccountData {
+        account_index: u8,
+        data_index: u8,
+        length: u8,
+    },
+}
+
+impl<'a> Seed<'a> {
+    fn packed_len(&self) -> usize {
+        match self {
+            Seed::Literal(bytes) => 2 + bytes.len(),
+            Seed::InstructionData { .. } => 3,
+            Seed::AccountKey { .. } => 2,
+            Seed::AccountData { .. } => 4,
+        }
+    }
+
+    fn pack(&self, dst: &mut [u8]) {
+        match *self {
+            Seed::Literal(bytes) => {
+                dst[..2].copy_from_slice(&[1, bytes.len() as u8]);
+                dst[2..2 + bytes.len()].copy_from_slice(bytes);
+            }
+            Seed::InstructionData { index, length } => {
+                dst[..3].copy_from_slice(&[2, index, length])
+            }
+            Seed::AccountKey { index } => dst[..2].copy_from_slice(&[3, index]),
+            Seed::AccountData {
+                account_index,
+                data_index,
+                length,
+            } => dst[..4].copy_from_slice(&[4, account_index, data_index, length]),
+        }
+    }
+
+    fn unpack(src: &'a [u8]) -> Result<Option<(Self, usize)>, Error> {
+        let seed = match src {
+            [] | [0, ..] => return Ok(None),
+            [1, len, rest @ ..] => Seed::Literal(
+                rest.get(..*len as usize)
+                    .ok_or(ProgramError::InvalidAccountData)?,
+            ),
+            [2, index, length, ..] => Seed::InstructionData {
+                index: *index,
+                length: *length,
+            },
+            [3, index, ..] => Seed::AccountKey { index: *index },
+            [4, account_index, data_index, length, ..] => Seed::AccountData {
+                account_index: *account_index,
+                data_index: *data_index,
+                length: *length,
+            },
+            _ => return Err(ProgramError::InvalidAccountData.into()),
+        };
+
+        Ok(Some((seed, seed.packed_len())))
+    }
+
+    /// Resolves the seed, the account data is copied in `buffer` to release its borrow.
+    fn resolve(
+        &self,
+        instruction_data: &'a [u8],
+        accounts: &'a [AccountInfo],
+        buffer: &'a mut [u8; MAX_SEED_LEN],
+    ) -> Result<&'a [u8], Error> {
+        let seed = match *self {
+            Seed::Literal(bytes) => Some(bytes),
+            Seed::InstructionData { index, length } => {
+                instruction_data.get(index as usize..index as usize + length as usize)
+            }
+            Seed::AccountKey { index } => accounts
+                .get(index as usize)
+                .map(|account| account.key().as_ref()),
+            Seed::AccountData {
+                account_index,
+                data_index,
+                length,
+            } => {
+                let account = accounts
+                    .get(account_index as usize)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let data = account.try_borrow_data()?;
+                let seed = data
+                    .get(data_index as usize..data_index as usize + length as usize)
+                    .ok_or(ProgramError::InvalidSeeds)?;
+                let buffer = buffer
+                    .get_mut(..seed.len())
+                    .ok_or(ProgramError::MaxSeedLengthExceeded)?;
+                buffer.copy_from_slice(seed);
+                Some(&*buffer)
+            }
+        };
+
+        seed.ok_or(ProgramError::InvalidSeeds.into())
+    }
+}
+
+/// Iterator over the seeds packed in an `ExtraAccountMeta` address config.
+pub struct SeedIter<'a> {
+    config: &'a [u8],
+}
+
+impl<'a> Iterator for SeedIter<'a> {
+    type Item = Result<Seed<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match Seed::unpack(self.config) {
+            Ok(Some((seed, len))) => {
+                self.config = &self.config[len..];
+                Some(Ok(seed))
+            }
+            Ok(None) => None,
+            Err(err) => {
+                self.config = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Key read from the instruction data or from the data of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PubkeyData {
+    InstructionData { index: u8 },
+    AccountData { account_index: u8, data_index: u8 },
+}
+
+/// Extra account required by a transfer hook, stored in the `ExtraAccountMetaList`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ExtraAccountMeta {
+    pub discriminator: u8,
+    pub address_config: [u8; ADDRESS_CONFIG_LEN],
+    pub is_signer: PodBool,
+    pub is_writable: PodBool,
+}
+
+impl ExtraAccountMeta {
+    pub fn new_with_pubkey(pubkey: &Pubkey, is_signer: bool, is_writable: bool) -> Self {
+        ExtraAccountMeta {
+            discriminator: PUBKEY_DISCRIMINATOR,
+            address_config: *pubkey,
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        }
+    }
+
+    /// PDA of the transfer hook program.
+    pub fn new_with_seeds(
+        seeds: &[Seed],
+        is_signer: bool,
+        is_writable: bool,
+    ) -> Result<Self, Error> {
+        Ok(ExtraAccountMeta {
+            discriminator: PDA_DISCRIMINATOR,
+            address_config: pack_seeds(seeds)?,
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        })
+    }
+
+    /// PDA of the program at `program_index` in the `Execute` accounts.
+    pub fn new_external_pda_with_seeds(
+        program_index: u8,
+        seeds: &[Seed],
+        is_signer: bool,
+        is_writable: bool,
+    ) -> Result<Self, Error> {
+        if program_index >= EXTERNAL_PDA_DISCRIMINATOR {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+
+        Ok(ExtraAccountMeta {
+            discriminator: EXTERNAL_PDA_DISCRIMINATOR + program_index,
+            address_config: pack_seeds(seeds)?,
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        })
+    }
+
+    pub fn new_with_pubkey_data(data: PubkeyData, is_signer: bool, is_writable: bool) -> Self {
+        let mut address_config = [0; ADDRESS_CONFIG_LEN];
+        match data {
+            PubkeyData::InstructionData { index } => {
+                address_config[..2].copy_from_slice(&[1, index])
+            }
+            PubkeyData::AccountData {
+                account_index,
+                data_index,
+            } => address_config[..3].copy_from_slice(&[2, account_index, data_index]),
+        }
+
+        ExtraAccountMeta {
+            discriminator: PUBKEY_DATA_DISCRIMINATOR,
+            address_config,
+            is_signer: is_signer.into(),
+            is_writable: is_writable.into(),
+        }
+    }
+
+    /// Seeds of the PDA, empty for the other kinds of extra accounts.
+    pub fn seeds(&self) -> SeedIter<'_> {
+        let config = match self.discriminator {
+            PDA_DISCRIMINATOR | EXTERNAL_PDA_DISCRIMINATOR.. => &self.address_config[..],
+            _ => &[],
+        };
+
+        SeedIter { config }
+    }
+
+    /// Resolves the address of the extra account, `accounts` are the `Execute` accounts
+    /// preceding it.
+    pub fn resolve(
+        &self,
+        instruction_data: &[u8],
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> Result<Pubkey, Error> {
+        match self.discriminator {
+            PUBKEY_DISCRIMINATOR => Ok(self.address_config),
+            PUBKEY_DATA_DISCRIMINATOR => self.resolve_pubkey_data(instruction_data, accounts),
+            PDA_DISCRIMINATOR => self.resolve_pda(instruction_data, accounts, program_id),
+            EXTERNAL_PDA_DISCRIMINATOR.. => {
+                let program_index = (self.discriminator - EXTERNAL_PDA_DISCRIMINATOR) as usize;
+                let program = accounts
+                    .get(program_index)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                self.resolve_pda(instruction_data, accounts, program.key())
+            }
+            _ => Err(ProgramError::InvalidAccountData.into()),
+        }
+    }
+
+    fn resolve_pda(
+        &self,
+        instruction_data: &[u8],
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> Result<Pubkey, Error> {
+        let mut buffers = [[0; MAX_SEED_LEN]; MAX_SEEDS];
+        let mut buffers = buffers.iter_mut();
+        let mut seeds: [&[u8]; MAX_SEEDS] = [&[]; MAX_SEEDS];
+        let mut len = 0;
+        for seed in self.seeds() {
+            let (slot, buffer) = seeds
+                .get_mut(len)
+                .zip(buffers.next())
+                .ok_or(ProgramError::MaxSeedLengthExceeded)?;
+            *slot = seed?.resolve(instruction_data, accounts, buffer)?;
+            len += 1;
+        }
+
+        Ok(find_program_address(&seeds[..len], program_id).0)
+    }
+
+    fn resolve_pubkey_data(
+        &self,
+        instruction_data: &[u8],
+        accounts: &[AccountInfo],
+    ) -> Result<Pubkey, Error> {
+        let key = match self.address_config {
+            [1, index, ..] => instruction_data
+                .get(index as usize..index as usize + 32)
+                .and_then(|key| key.try_into().ok()),
+            [2, account_index, data_index, ..] => {
+                let account = accounts
+                    .get(account_index as usize)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let data = account.try_borrow_data()?;
+                data.get(data_index as usize..data_index as usize + 32)
+                    .and_then(|key| key.try_into().ok())
+            }
+            _ => return Err(ProgramError::InvalidAccountData.into()),
+        };
+
+        key.ok_or(ProgramError::InvalidAccountData.into())
+    }
+}
+
+fn pack_seeds(seeds: &[Seed]) -> Result<[u8; ADDRESS_CONFIG_LEN], Error> {
+    let mut config = [0; ADDRESS_CONFIG_LEN];
+    let mut offset = 0;
+    for seed in seeds {
+        let len = seed.packed_len();
+        let dst = config
+            .get_mut(offset..offset + len)
+            .ok_or(ProgramError::InvalidArgument)?;
+        seed.pack(dst);
+        offset += len;
+    }
+
+    Ok(config)
+}
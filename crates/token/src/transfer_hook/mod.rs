@@ -0,0 +1,195 @@
+use {
+    bytemuck::{try_cast_slice, try_cast_slice_mut},
+    core::mem::size_of,
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::Signer as CpiSigner,
+        program_error::ProgramError,
+        pubkey::{find_program_address, Pubkey},
+        sysvars::rent::Rent,
+    },
+    typhoon_accounts::WritableAccount,
+    typhoon_errors::Error,
+    typhoon_utility::create_or_assign,
+};
+
+mod execute;
+mod extra_account_meta;
+
+pub use {execute::*, extra_account_meta::*};
+
+/// `sha256("spl-transfer-hook-interface:execute")[..8]`
+pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+/// `sha256("spl-transfer-hook-interface:initialize-extra-account-metas")[..8]`
+pub const INITIALIZE_EXTRA_ACCOUNT_METAS_DISCRIMINATOR: [u8; 8] =
+    [43, 34, 13, 49, 167, 88, 235, 235];
+/// `sha256("spl-transfer-hook-interface:update-extra-account-metas")[..8]`
+pub const UPDATE_EXTRA_ACCOUNT_METAS_DISCRIMINATOR: [u8; 8] =
+    [157, 105, 42, 146, 102, 85, 241, 174];
+
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+#[inline]
+pub fn find_extra_account_metas_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], program_id)
+}
+
+/// Layout of the validation account: the `Execute` discriminator, the length of the value, the
+/// number of metas and the metas themselves.
+pub struct ExtraAccountMetaList;
+
+impl ExtraAccountMetaList {
+    const HEADER_LEN: usize = EXECUTE_DISCRIMINATOR.len() + 2 * size_of::<u32>();
+
+    #[inline(always)]
+    pub const fn size_of(num_metas: usize) -> usize {
+        Self::HEADER_LEN + num_metas * size_of::<ExtraAccountMeta>()
+    }
+
+    /// Writes `metas` in `data`, which must be at least `ExtraAccountMetaList::size_of(metas.len())`.
+    pub fn init(data: &mut [u8], metas: &[ExtraAccountMeta]) -> Result<(), Error> {
+        let len = Self::size_of(metas.len());
+        let data = data
+            .get_mut(..len)
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+
+        let (header, value) = data.split_at_mut(Self::HEADER_LEN);
+        header[..8].copy_from_slice(&EXECUTE_DISCRIMINATOR);
+        header[8..12].copy_from_slice(&((len - 12) as u32).to_le_bytes());
+        header[12..].copy_from_slice(&(metas.len() as u32).to_le_bytes());
+
+        try_cast_slice_mut::<u8, ExtraAccountMeta>(value)
+            .map_err(|_| ProgramError::InvalidAccountData)?
+            .copy_from_slice(metas);
+
+        Ok(())
+    }
+
+    /// Reads the metas of the `Execute` instruction from the validation account data.
+    pub fn unpack(data: &[u8]) -> Result<&[ExtraAccountMeta], Error> {
+        let header = data
+            .get(..Self::HEADER_LEN)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if header[..8] != EXECUTE_DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        let count = u32::from_le_bytes([header[12], header[13], header[14], header[15]]) as usize;
+        let value = data
+            .get(Self::HEADER_LEN..Self::size_of(count))
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        try_cast_slice(value).map_err(|_| ProgramError::InvalidAccountData.into())
+    }
+
+    /// Creates the validation account of `mint` and writes `metas` in it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        account: &AccountInfo,
+        rent: &Rent,
+        payer: &impl WritableAccount,
+        mint: &Pubkey,
+        bump: u8,
+        program_id: &Pubkey,
+        metas: &[ExtraAccountMeta],
+    ) -> Result<(), Error> {
+        let bump = [bump];
+        let seeds = pinocchio::seeds!(EXTRA_ACCOUNT_METAS_SEED, mint.as_ref(), &bump);
+        let signer = CpiSigner::from(&seeds);
+
+        create_or_assign(
+            account,
+            rent,
+            payer,
+            program_id,
+            Self::size_of(metas.len()),
+            Some(&[signer]),
+        )?;
+
+        Self::init(&mut account.try_borrow_mut_data()?, metas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_account_meta_list_layout() {
+        let key = Pubkey::from([9; 32]);
+        let metas = [
+            ExtraAccountMeta::new_with_pubkey(&key, false, true),
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal(b"counter"),
+                    Seed::AccountKey { index: 3 },
+                    Seed::InstructionData {
+                        index: 8,
+                        length: 8,
+                    },
+                ],
+                false,
+                true,
+            )
+            .unwrap_or_else(|_| panic!("seeds should fit in the config")),
+        ];
+
+        assert_eq!(size_of::<ExtraAccountMeta>(), 35);
+        assert_eq!(ExtraAccountMetaList::size_of(2), 8 + 4 + 4 + 70);
+
+        let mut data = [0; ExtraAccountMetaList::size_of(2)];
+        let Ok(()) = ExtraAccountMetaList::init(&mut data, &metas) else {
+            panic!("init failed");
+        };
+        assert_eq!(&data[..8], &EXECUTE_DISCRIMINATOR);
+        assert_eq!(&data[8..12], &74u32.to_le_bytes());
+
+        let Ok(unpacked) = ExtraAccountMetaList::unpack(&data) else {
+            panic!("unpack failed");
+        };
+        assert_eq!(unpacked, &metas);
+
+        let seeds = unpacked[1]
+            .seeds()
+            .map(|seed| seed.unwrap_or_else(|_| panic!("invalid seed")));
+        assert!(seeds.eq([
+            Seed::Literal(b"counter"),
+            Seed::AccountKey { index: 3 },
+            Seed::InstructionData {
+                index: 8,
+                length: 8
+            },
+        ]));
+        assert_eq!(unpacked[0].seeds().count(), 0);
+    }
+
+    #[test]
+    fn seeds_exceeding_config() {
+        let seeds = [Seed::Literal(&[1; 31])];
+        assert!(ExtraAccountMeta::new_with_seeds(&seeds, false, false).is_err());
+        assert!(ExtraAccountMeta::new_external_pda_with_seeds(128, &[], false, false).is_err());
+    }
+
+    #[test]
+    fn resolve_pubkey_data() {
+        let key = Pubkey::from([4; 32]);
+        let mut instruction_data = [0; 48];
+        instruction_data[16..].copy_from_slice(&key);
+
+        let meta = ExtraAccountMeta::new_with_pubkey_data(
+            PubkeyData::InstructionData { index: 16 },
+            false,
+            false,
+        );
+        let Ok(resolved) = meta.resolve(&instruction_data, &[], &Pubkey::default()) else {
+            panic!("resolve failed");
+        };
+        assert_eq!(resolved, key);
+
+        let fixed = ExtraAccountMeta::new_with_pubkey(&key, false, false);
+        let Ok(resolved) = fixed.resolve(&[], &[], &Pubkey::default()) else {
+            panic!("resolve failed");
+        };
+        assert_eq!(resolved, key);
+    }
+}
@@ -0,0 +1,178 @@
+use {
+    bytemuck::NoUninit,
+    pinocchio::{
+        account_info::AccountInfo,
+        cpi::invoke_signed,
+        instruction::{AccountMeta, Instruction, Seed, Signer},
+        log::sol_log_data,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    pinocchio_pubkey::derive_address,
+    typhoon_accounts::Discriminator,
+    typhoon_errors::{Error, ErrorCode},
+    typhoon_utility::bytes::{MaybeUninitWriter, UNINIT_BYTE},
+};
+
+/// Maximum serialized size of an event, the buffer lives on the stack.
+pub const MAX_EVENT_SIZE: usize = 1024;
+
+/// Instruction data prefix reserved for self-CPI events.
+pub const EVENT_IX_TAG: [u8; 8] = 0x1d9acb512ea545e4_u64.to_le_bytes();
+
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Accounts needed to emit an event through a self-CPI, implemented by
+/// `#[event_cpi]` contexts.
+pub trait EventCpiContext {
+    fn event_authority(&self) -> &AccountInfo;
+    fn event_authority_bump(&self) -> u8;
+    fn event_program(&self) -> &AccountInfo;
+}
+
+pub trait Event: Discriminator {
+    /// Logs the discriminator followed by the raw bytes of the event. Never fails, the result
+    /// matches the one of [`Event::emit_borsh`].
+    #[inline(always)]
+    fn emit(&self) -> Result<(), Error>
+    where
+        Self: NoUninit,
+    {
+        sol_log_data(&[Self::DISCRIMINATOR, bytemuck::bytes_of(self)]);
+
+        Ok(())
+    }
+
+    /// Logs the discriminator followed by the borsh serialized event.
+    #[cfg(feature = "borsh")]
+    #[inline(always)]
+    fn emit_borsh(&self) -> Result<(), Error>
+    where
+        Self: borsh::BorshSerialize,
+    {
+        let mut buffer = [UNINIT_BYTE; MAX_EVENT_SIZE];
+        let mut writer = MaybeUninitWriter::new(&mut buffer, 0);
+        self.serialize(&mut writer)
+            .map_err(|_| ProgramError::BorshIoError)?;
+
+        sol_log_data(&[Self::DISCRIMINATOR, writer.initialized()]);
+
+        Ok(())
+    }
+
+    /// Invokes the program itself with the event as instruction data, so the
+    /// event is recorded in the inner instructions instead of the logs.
+    #[inline(always)]
+    fn emit_cpi(&self, ctx: &impl EventCpiContext) -> Result<(), Error>
+    where
+        Self: NoUninit,
+    {
+        let mut buffer = [UNINIT_BYTE; MAX_EVENT_SIZE];
+        let mut writer = event_cpi_writer::<Self>(&mut buffer, ctx.event_authority_bump())?;
+        write_all(&mut writer, bytemuck::bytes_of(self))?;
+
+        invoke_event_cpi(ctx, writer.initialized())
+    }
+
+    /// Same as [`Event::emit_cpi`] with a borsh serialized event.
+    #[cfg(feature = "borsh")]
+    #[inline(always)]
+    fn emit_cpi_borsh(&self, ctx: &impl EventCpiContext) -> Result<(), Error>
+    where
+        Self: borsh::BorshSerialize,
+    {
+        let mut buffer = [UNINIT_BYTE; MAX_EVENT_SIZE];
+        let mut writer = event_cpi_writer::<Self>(&mut buffer, ctx.event_authority_bump())?;
+        self.serialize(&mut writer)
+            .map_err(|_| ProgramError::BorshIoError)?;
+
+        invoke_event_cpi(ctx, writer.initialized())
+    }
+}
+
+/// Writes the `[tag, bump, discriminator]` header of a self-CPI event.
+#[inline(always)]
+fn event_cpi_writer<T: Discriminator + ?Sized>(
+    buffer: &mut [core::mem::MaybeUninit<u8>],
+    bump: u8,
+) -> Result<MaybeUninitWriter<'_>, Error> {
+    let mut writer = MaybeUninitWriter::new(buffer, 0);
+    write_all(&mut writer, &EVENT_IX_TAG)?;
+    write_all(&mut writer, &[bump])?;
+    write_all(&mut writer, T::DISCRIMINATOR)?;
+
+    Ok(writer)
+}
+
+#[inline(always)]
+fn write_all(writer: &mut MaybeUninitWriter, data: &[u8]) -> Result<(), Error> {
+    if writer.write_bytes(data)? != data.len() {
+        return Err(ErrorCode::BufferFull.into());
+    }
+
+    Ok(())
+}
+
+#[inline(always)]
+fn invoke_event_cpi(ctx: &impl EventCpiContext, data: &[u8]) -> Result<(), Error> {
+    let event_authority = ctx.event_authority();
+    let bump = [ctx.event_authority_bump()];
+    let seeds = [Seed::from(EVENT_AUTHORITY_SEED), Seed::from(&bump)];
+
+    invoke_signed(
+        &Instruction {
+            program_id: ctx.event_program().key(),
+            accounts: &[AccountMeta::readonly_signer(event_authority.key())],
+            data,
+        },
+        &[event_authority],
+        &[Signer::from(&seeds)],
+    )
+    .map_err(Into::into)
+}
+
+/// Handles the self-CPI of an event, the instruction is only accepted when it
+/// is signed by the event authority of the program.
+#[inline(always)]
+pub fn handle_event_cpi(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), Error> {
+    let [event_authority, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys.into());
+    };
+    let bump = *instruction_data
+        .get(EVENT_IX_TAG.len())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    if !event_authority.is_signer()
+        || event_authority.key() != &derive_address(&[EVENT_AUTHORITY_SEED], Some(bump), program_id)
+    {
+        return Err(ErrorCode::InvalidEventAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Emits an event through `sol_log_data`, prefix with `borsh` for borsh events.
+#[macro_export]
+macro_rules! emit {
+    (borsh $event:expr) => {
+        $crate::Event::emit_borsh(&$event)
+    };
+    ($event:expr) => {
+        $crate::Event::emit(&$event)
+    };
+}
+
+/// Emits an event through a self-CPI, the context needs the `#[event_cpi]` attribute.
+#[macro_export]
+macro_rules! emit_cpi {
+    ($ctx:expr, borsh $event:expr) => {
+        $crate::Event::emit_cpi_borsh(&$event, &$ctx)
+    };
+    ($ctx:expr, $event:expr) => {
+        $crate::Event::emit_cpi(&$event, &$ctx)
+    };
+}
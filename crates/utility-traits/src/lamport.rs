@@ -1,7 +1,8 @@
 use {
-    pinocchio::program_error::ProgramError,
+    pinocchio::{program_error::ProgramError, sysvars::rent::Rent},
     typhoon_accounts::{
-        Mut, Signer, SignerAccount, SignerCheck, SystemAccount, UncheckedAccount, WritableAccount,
+        Account, Discriminator, Mut, ProgramOwned, RefFromBytes, Signer, SignerAccount,
+        SignerCheck, SystemAccount, UncheckedAccount, WritableAccount,
     },
     typhoon_errors::Error,
 };
@@ -41,3 +42,115 @@ pub trait LamportsChecked: WritableAccount + SignerAccount {
 
 impl<C: SignerCheck> LamportsChecked for Mut<Signer<'_, SystemAccount<'_>, C>> {}
 impl<C: SignerCheck> LamportsChecked for Mut<Signer<'_, UncheckedAccount<'_>, C>> {}
+
+/// Moves lamports out of an account owned by the program by editing the balances directly, the
+/// runtime rejects the debit of an account owned by another program.
+pub trait WithdrawLamports: WritableAccount {
+    /// Sends `amount` lamports to `to`, the account must stay rent-exempt unless it is emptied.
+    #[inline(always)]
+    fn withdraw(&self, to: &impl WritableAccount, amount: u64, rent: &Rent) -> Result<(), Error> {
+        let mut source_lamports = self.mut_lamports()?;
+        let remaining = source_lamports
+            .checked_sub(amount)
+            .ok_or(ProgramError::InsufficientFunds)?;
+
+        if remaining != 0 && remaining < rent.minimum_balance(self.as_ref().data_len()) {
+            return Err(ProgramError::AccountNotRentExempt.into());
+        }
+
+        let mut recipient_lamports = to.mut_lamports()?;
+        *recipient_lamports = recipient_lamports
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *source_lamports = remaining;
+
+        Ok(())
+    }
+
+    /// Sends every lamport above the rent-exempt minimum to `to` and returns the amount sent.
+    #[inline(always)]
+    fn withdraw_excess(&self, to: &impl WritableAccount, rent: &Rent) -> Result<u64, Error> {
+        let minimum_balance = rent.minimum_balance(self.as_ref().data_len());
+        let amount = self.lamports()?.saturating_sub(minimum_balance);
+        self.withdraw(to, amount, rent)?;
+
+        Ok(amount)
+    }
+}
+
+impl<T: ProgramOwned + Discriminator + RefFromBytes> WithdrawLamports for Mut<Account<'_, T>> {}
+impl<T: ProgramOwned + Discriminator + RefFromBytes, C: SignerCheck> WithdrawLamports
+    for Mut<Signer<'_, Account<'_, T>, C>>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{account_infos, ok, rent, TestAccount},
+        bytemuck::{AnyBitPattern, NoUninit},
+        pinocchio::pubkey::Pubkey,
+        typhoon_accounts::{FromAccountInfo, Owner, ReadableAccount},
+    };
+
+    #[derive(NoUninit, AnyBitPattern, Clone, Copy)]
+    #[repr(C)]
+    struct Vault {
+        amount: u64,
+    }
+
+    impl Owner for Vault {
+        const OWNER: Pubkey = [7; 32];
+    }
+
+    impl ProgramOwned for Vault {}
+
+    impl Discriminator for Vault {
+        const DISCRIMINATOR: &'static [u8] = &[1];
+    }
+
+    #[test]
+    fn withdraw_keeps_rent_exemption_or_empties() {
+        let rent = rent();
+        let minimum_balance = rent.minimum_balance(9);
+        let infos = account_infos(&[
+            TestAccount {
+                owner: Vault::OWNER,
+                lamports: minimum_balance + 1_000,
+                data: &[1, 0, 0, 0, 0, 0, 0, 0, 0],
+            },
+            TestAccount {
+                owner: pinocchio_system::ID,
+                lamports: 0,
+                data: &[],
+            },
+        ]);
+        let vault = ok(Mut::<Account<Vault>>::try_from_info(&infos[0]));
+        let recipient = ok(Mut::<UncheckedAccount>::try_from_info(&infos[1]));
+
+        ok(vault.withdraw(&recipient, 400, &rent));
+        assert_eq!(*ok(vault.lamports()), minimum_balance + 600);
+        assert_eq!(*ok(recipient.lamports()), 400);
+
+        assert_eq!(
+            vault
+                .withdraw(&recipient, 601, &rent)
+                .map_err(ProgramError::from),
+            Err(ProgramError::AccountNotRentExempt)
+        );
+        assert_eq!(
+            vault
+                .withdraw(&recipient, minimum_balance + 601, &rent)
+                .map_err(ProgramError::from),
+            Err(ProgramError::InsufficientFunds)
+        );
+
+        assert_eq!(ok(vault.withdraw_excess(&recipient, &rent)), 600);
+        assert_eq!(*ok(vault.lamports()), minimum_balance);
+
+        ok(vault.withdraw(&recipient, minimum_balance, &rent));
+        assert_eq!(*ok(vault.lamports()), 0);
+        assert_eq!(*ok(recipient.lamports()), minimum_balance + 1_000);
+    }
+}
@@ -2,7 +2,11 @@
 
 mod close;
 mod create;
+mod event;
 mod lamport;
+mod realloc;
 mod system;
+#[cfg(test)]
+mod test_utils;
 
-pub use {close::*, create::*, lamport::*, system::*};
+pub use {close::*, create::*, event::*, lamport::*, realloc::*, system::*};
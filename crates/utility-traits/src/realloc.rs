@@ -0,0 +1,97 @@
+use {
+    pinocchio::{program_error::ProgramError, sysvars::rent::Rent},
+    pinocchio_system::instructions::Transfer,
+    typhoon_accounts::WritableAccount,
+    typhoon_errors::Error,
+};
+
+pub trait ReallocAccount: WritableAccount {
+    /// Resizes the account data to `new_len` and keeps it rent exempt.
+    ///
+    /// When the account grows, the missing lamports are transferred from `payer`, which needs to be
+    /// a signer owned by the system program. When it shrinks, the rent of the removed bytes is
+    /// refunded to `payer`. New bytes are always zero-initialized.
+    #[inline(always)]
+    fn realloc(
+        &self,
+        new_len: usize,
+        payer: &impl WritableAccount,
+        rent: &Rent,
+    ) -> Result<(), Error> {
+        let old_len = self.as_ref().data_len();
+        let required_lamports = rent.minimum_balance(new_len);
+        let current_lamports = *self.lamports()?;
+
+        if new_len > old_len {
+            if required_lamports > current_lamports {
+                Transfer {
+                    from: payer.as_ref(),
+                    to: self.as_ref(),
+                    lamports: required_lamports - current_lamports,
+                }
+                .invoke()?;
+            }
+        } else if new_len < old_len {
+            // Only the rent of the removed bytes is refunded, the lamports above the rent minimum
+            // stay in the account.
+            let refund = (rent.minimum_balance(old_len) - required_lamports)
+                .min(current_lamports.saturating_sub(required_lamports));
+            if refund > 0 {
+                let payer_lamports = *payer.lamports()?;
+
+                *payer.mut_lamports()? = payer_lamports
+                    .checked_add(refund)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                *self.mut_lamports()? = current_lamports - refund;
+            }
+        }
+
+        self.resize(new_len)
+    }
+}
+
+impl<T: WritableAccount> ReallocAccount for T {}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::test_utils::{account_infos, ok, rent, TestAccount},
+        typhoon_accounts::{FromAccountInfo, Mut, ReadableAccount, UncheckedAccount},
+    };
+
+    #[test]
+    fn realloc_refunds_only_removed_rent() {
+        let rent = rent();
+        let surplus = 10_000;
+        let vault_lamports = rent.minimum_balance(100) + surplus;
+        let infos = account_infos(&[
+            TestAccount {
+                owner: [7; 32],
+                lamports: vault_lamports,
+                data: &[1; 100],
+            },
+            TestAccount {
+                owner: pinocchio_system::ID,
+                lamports: 1_000_000,
+                data: &[],
+            },
+        ]);
+        let vault = ok(Mut::<UncheckedAccount>::try_from_info(&infos[0]));
+        let payer = ok(Mut::<UncheckedAccount>::try_from_info(&infos[1]));
+
+        ok(vault.realloc(100, &payer, &rent));
+        assert_eq!(*ok(vault.lamports()), vault_lamports);
+
+        // The surplus already covers the rent of the new bytes.
+        ok(vault.realloc(101, &payer, &rent));
+        assert_eq!(*ok(vault.lamports()), vault_lamports);
+        assert_eq!(*ok(payer.lamports()), 1_000_000);
+
+        ok(vault.realloc(50, &payer, &rent));
+        let refund = rent.minimum_balance(101) - rent.minimum_balance(50);
+        assert_eq!(*ok(vault.lamports()), vault_lamports - refund);
+        assert_eq!(*ok(payer.lamports()), 1_000_000 + refund);
+        assert_eq!(vault.as_ref().data_len(), 50);
+    }
+}
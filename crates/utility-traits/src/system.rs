@@ -1,5 +1,5 @@
 use {
-    pinocchio::{account_info::AccountInfo, pubkey::Pubkey},
+    pinocchio::{account_info::AccountInfo, instruction::Signer as CpiSigner, pubkey::Pubkey},
     pinocchio_system::instructions::{Allocate, Assign, Transfer},
     typhoon_accounts::{
         Mut, Signer as SignerAccount, SignerCheck, SystemAccount, UncheckedAccount, WritableAccount,
@@ -43,5 +43,51 @@ where
     }
 }
 
+/// System instructions of a PDA owned by the system program, signed by the seeds of the account.
+pub trait SystemCpiSigned<'a>: WritableAccount + Into<&'a AccountInfo>
+where
+    Self: Sized,
+{
+    /// [`SystemCpi::allocate`] signed by the PDA seeds of the account.
+    #[inline(always)]
+    fn allocate_signed(&self, new_space: u64, seeds: &[CpiSigner]) -> Result<(), Error> {
+        Allocate {
+            account: self.as_ref(),
+            space: new_space,
+        }
+        .invoke_signed(seeds)
+        .map_err(Into::into)
+    }
+
+    /// [`SystemCpi::assign`] signed by the PDA seeds of the account.
+    #[inline(always)]
+    fn assign_signed(&self, owner: &Pubkey, seeds: &[CpiSigner]) -> Result<(), Error> {
+        Assign {
+            account: self.as_ref(),
+            owner,
+        }
+        .invoke_signed(seeds)
+        .map_err(Into::into)
+    }
+
+    /// [`SystemCpi::transfer`] signed by the PDA seeds of the account.
+    #[inline(always)]
+    fn transfer_signed(
+        &self,
+        to: &impl WritableAccount,
+        amount: u64,
+        seeds: &[CpiSigner],
+    ) -> Result<(), Error> {
+        Transfer {
+            from: self.as_ref(),
+            lamports: amount,
+            to: to.as_ref(),
+        }
+        .invoke_signed(seeds)
+        .map_err(Into::into)
+    }
+}
+
 impl<'a, C: SignerCheck> SystemCpi<'a> for Mut<SignerAccount<'a, SystemAccount<'a>, C>> {}
 impl<'a, C: SignerCheck> SystemCpi<'a> for Mut<SignerAccount<'a, UncheckedAccount<'a>, C>> {}
+impl<'a> SystemCpiSigned<'a> for Mut<SystemAccount<'a>> {}
@@ -0,0 +1,74 @@
+extern crate std;
+
+use {
+    core::mem::MaybeUninit,
+    pinocchio::{
+        account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE},
+        entrypoint::deserialize,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::rent::Rent,
+    },
+    std::{boxed::Box, vec, vec::Vec},
+    typhoon_errors::Error,
+};
+
+const MAX_ACCOUNTS: usize = 8;
+
+pub struct TestAccount<'a> {
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: &'a [u8],
+}
+
+/// Serializes the accounts the way the runtime does, all of them are writable signers. The input
+/// buffer is leaked to outlive the test.
+pub fn account_infos(accounts: &[TestAccount]) -> Vec<AccountInfo> {
+    let mut input = Vec::new();
+    input.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for (i, account) in accounts.iter().enumerate() {
+        input.extend_from_slice(&[u8::MAX, 1, 1, 0, 0, 0, 0, 0]);
+        input.extend_from_slice(&[i as u8; 32]);
+        input.extend_from_slice(&account.owner);
+        input.extend_from_slice(&account.lamports.to_le_bytes());
+        input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        input.extend_from_slice(account.data);
+        input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        input.resize(input.len().next_multiple_of(8), 0);
+        input.extend_from_slice(&u64::MAX.to_le_bytes());
+    }
+    input.extend_from_slice(&0u64.to_le_bytes());
+    input.extend_from_slice(&Pubkey::default());
+
+    let buffer = Box::leak(vec![0u64; input.len().div_ceil(8)].into_boxed_slice());
+    let ptr = buffer.as_mut_ptr() as *mut u8;
+    // SAFETY: the buffer is large enough, aligned and lives until the end of the test.
+    unsafe { core::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+
+    let mut infos = [MaybeUninit::<AccountInfo>::uninit(); MAX_ACCOUNTS];
+    // SAFETY: the input follows the layout of the runtime.
+    let (_, len, _) = unsafe { deserialize::<MAX_ACCOUNTS>(ptr, &mut infos) };
+
+    // SAFETY: `deserialize` initialized the first `len` accounts.
+    infos[..len]
+        .iter()
+        .map(|info| unsafe { info.assume_init() })
+        .collect()
+}
+
+/// Default rent of the cluster.
+pub fn rent() -> Rent {
+    let mut bytes = [0u64; 3];
+    bytes[0] = 3480;
+    bytes[1] = 2.0f64.to_bits();
+    bytes[2] = 50;
+
+    // SAFETY: the buffer is aligned and holds a valid `Rent`.
+    let bytes = unsafe { core::slice::from_raw_parts(bytes.as_ptr() as *const u8, Rent::LEN) };
+    *Rent::from_bytes(bytes).unwrap()
+}
+
+/// Unwraps the result, `Error` does not implement `Debug`.
+pub fn ok<T>(result: Result<T, Error>) -> T {
+    result.unwrap_or_else(|err| panic!("{:?}", ProgramError::from(err)))
+}
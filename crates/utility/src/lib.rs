@@ -1,7 +1,11 @@
 #![no_std]
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
 mod create_account;
 
 pub mod bytes;
+pub mod signature;
 
 pub use create_account::*;
@@ -0,0 +1,182 @@
+#[cfg(any(test, feature = "std"))]
+use std::vec::Vec;
+use {
+    super::{instruction_slice, read_u16},
+    pinocchio::pubkey::{pubkey_eq, Pubkey},
+    pinocchio_pubkey::pubkey,
+    typhoon_accounts::{Instructions, IntrospectedInstruction},
+    typhoon_errors::{Error, ErrorCode},
+};
+
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+pub const ED25519_SIGNATURE_SIZE: usize = 64;
+pub const ED25519_SIGNATURE_OFFSETS_SIZE: usize = 14;
+pub const ED25519_SIGNATURE_OFFSETS_START: usize = 2;
+
+/// Offsets of a signature in an Ed25519 program instruction, an instruction index of
+/// `u16::MAX` targets the Ed25519 instruction itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    #[inline(always)]
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < ED25519_SIGNATURE_OFFSETS_SIZE {
+            return None;
+        }
+
+        Some(Ed25519SignatureOffsets {
+            signature_offset: read_u16(data, 0),
+            signature_instruction_index: read_u16(data, 2),
+            public_key_offset: read_u16(data, 4),
+            public_key_instruction_index: read_u16(data, 6),
+            message_data_offset: read_u16(data, 8),
+            message_data_size: read_u16(data, 10),
+            message_instruction_index: read_u16(data, 12),
+        })
+    }
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> [u8; ED25519_SIGNATURE_OFFSETS_SIZE] {
+        let mut bytes = [0; ED25519_SIGNATURE_OFFSETS_SIZE];
+        for (chunk, value) in bytes.chunks_exact_mut(2).zip([
+            self.signature_offset,
+            self.signature_instruction_index,
+            self.public_key_offset,
+            self.public_key_instruction_index,
+            self.message_data_offset,
+            self.message_data_size,
+            self.message_instruction_index,
+        ]) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// A signature verified by an Ed25519 program instruction.
+pub struct Ed25519Signature<'a> {
+    pub public_key: &'a Pubkey,
+    pub signature: &'a [u8; ED25519_SIGNATURE_SIZE],
+    pub message: &'a [u8],
+}
+
+#[inline(always)]
+fn ed25519_index(index: u16) -> Option<usize> {
+    (index != u16::MAX).then_some(index as usize)
+}
+
+/// Iterates over the signatures verified by an Ed25519 program instruction.
+pub fn ed25519_signatures<'a>(
+    instructions: &'a Instructions,
+    instruction: IntrospectedInstruction<'a>,
+) -> impl Iterator<Item = Result<Ed25519Signature<'a>, Error>> + 'a {
+    let data = instruction.data();
+    let count = data.first().copied().unwrap_or_default() as usize;
+
+    (0..count).map(move |i| {
+        let start = ED25519_SIGNATURE_OFFSETS_START + i * ED25519_SIGNATURE_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..)
+            .and_then(Ed25519SignatureOffsets::from_bytes)
+            .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+
+        let signature = instruction_slice(
+            instructions,
+            data,
+            ed25519_index(offsets.signature_instruction_index),
+            offsets.signature_offset,
+            ED25519_SIGNATURE_SIZE,
+        )?;
+        let public_key = instruction_slice(
+            instructions,
+            data,
+            ed25519_index(offsets.public_key_instruction_index),
+            offsets.public_key_offset,
+            core::mem::size_of::<Pubkey>(),
+        )?;
+        let message = instruction_slice(
+            instructions,
+            data,
+            ed25519_index(offsets.message_instruction_index),
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        Ok(Ed25519Signature {
+            // SAFETY: The slices have the exact size of the arrays.
+            public_key: unsafe { &*(public_key.as_ptr() as *const Pubkey) },
+            signature: unsafe { &*(signature.as_ptr() as *const [u8; ED25519_SIGNATURE_SIZE]) },
+            message,
+        })
+    })
+}
+
+/// Checks that an Ed25519 program instruction of the transaction verified `signature` of
+/// `message` by `public_key`.
+pub fn require_ed25519_signature(
+    instructions: &Instructions,
+    public_key: &Pubkey,
+    message: &[u8],
+    signature: &[u8; ED25519_SIGNATURE_SIZE],
+) -> Result<(), Error> {
+    for instruction in instructions.iter() {
+        let instruction = instruction?;
+        if !pubkey_eq(instruction.program_id(), &ED25519_PROGRAM_ID) {
+            continue;
+        }
+
+        for verified in ed25519_signatures(instructions, instruction) {
+            let verified = verified?;
+            if pubkey_eq(verified.public_key, public_key)
+                && verified.signature == signature
+                && verified.message == message
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(ErrorCode::SignatureNotVerified.into())
+}
+
+/// Builds the data of an Ed25519 program instruction verifying one signature.
+#[cfg(any(test, feature = "std"))]
+pub fn new_ed25519_instruction_data(
+    public_key: &Pubkey,
+    message: &[u8],
+    signature: &[u8; ED25519_SIGNATURE_SIZE],
+) -> Vec<u8> {
+    let public_key_offset = ED25519_SIGNATURE_OFFSETS_START + ED25519_SIGNATURE_OFFSETS_SIZE;
+    let signature_offset = public_key_offset + public_key.len();
+    let message_data_offset = signature_offset + ED25519_SIGNATURE_SIZE;
+
+    let offsets = Ed25519SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: u16::MAX,
+        public_key_offset: public_key_offset as u16,
+        public_key_instruction_index: u16::MAX,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: u16::MAX,
+    };
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    data.extend_from_slice(&offsets.to_bytes());
+    data.extend_from_slice(public_key);
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    data
+}
@@ -0,0 +1,114 @@
+mod ed25519;
+mod secp256k1;
+
+pub use {ed25519::*, secp256k1::*};
+use {
+    typhoon_accounts::Instructions,
+    typhoon_errors::{Error, ErrorCode},
+};
+
+/// Returns `size` bytes at `offset` of the data of the instruction at `index`, `None` targets the
+/// verify instruction itself.
+#[inline(always)]
+fn instruction_slice<'a>(
+    instructions: &'a Instructions,
+    current_data: &'a [u8],
+    index: Option<usize>,
+    offset: u16,
+    size: usize,
+) -> Result<&'a [u8], Error> {
+    let data = match index {
+        Some(index) => instructions.load_instruction_at(index)?.data(),
+        None => current_data,
+    };
+    let start = offset as usize;
+
+    data.get(start..start + size)
+        .ok_or(ErrorCode::InvalidSignatureInstruction.into())
+}
+
+#[inline(always)]
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, pinocchio::pubkey::Pubkey, std::vec::Vec, typhoon_accounts::SysvarData};
+
+    fn instructions_data(ixs: &[(Pubkey, Vec<u8>)], current: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(ixs.len() as u16).to_le_bytes());
+        let mut body = Vec::new();
+        for (program_id, ix_data) in ixs {
+            let offset = 2 + 2 * ixs.len() + body.len();
+            data.extend_from_slice(&(offset as u16).to_le_bytes());
+            body.extend_from_slice(&0u16.to_le_bytes());
+            body.extend_from_slice(program_id);
+            body.extend_from_slice(&(ix_data.len() as u16).to_le_bytes());
+            body.extend_from_slice(ix_data);
+        }
+        data.extend_from_slice(&body);
+        data.extend_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_ed25519_signature() {
+        let public_key = [3; 32];
+        let signature = [4; 64];
+        let data = instructions_data(
+            &[
+                (
+                    ED25519_PROGRAM_ID,
+                    new_ed25519_instruction_data(&public_key, b"permit", &signature),
+                ),
+                ([7; 32], Vec::from([0])),
+            ],
+            1,
+        );
+        let Ok(instructions) = Instructions::from_bytes(&data) else {
+            panic!("invalid instructions sysvar");
+        };
+
+        assert!(
+            require_ed25519_signature(instructions, &public_key, b"permit", &signature).is_ok()
+        );
+        assert!(
+            require_ed25519_signature(instructions, &public_key, b"other", &signature).is_err()
+        );
+        assert!(require_ed25519_signature(instructions, &[5; 32], b"permit", &signature).is_err());
+        assert!(require_ed25519_signature(instructions, &public_key, b"permit", &[0; 64]).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_signature() {
+        let eth_address = [1; 20];
+        let signature = [2; 64];
+        let data = instructions_data(
+            &[
+                ([7; 32], Vec::from([0])),
+                (
+                    SECP256K1_PROGRAM_ID,
+                    new_secp256k1_instruction_data(&eth_address, b"oracle", &signature, 1, 1),
+                ),
+            ],
+            0,
+        );
+        let Ok(instructions) = Instructions::from_bytes(&data) else {
+            panic!("invalid instructions sysvar");
+        };
+
+        assert!(
+            require_secp256k1_signature(instructions, &eth_address, b"oracle", &signature, 1)
+                .is_ok()
+        );
+        assert!(
+            require_secp256k1_signature(instructions, &eth_address, b"oracle", &signature, 0)
+                .is_err()
+        );
+        assert!(
+            require_secp256k1_signature(instructions, &[0; 20], b"oracle", &signature, 1).is_err()
+        );
+    }
+}
@@ -0,0 +1,184 @@
+#[cfg(any(test, feature = "std"))]
+use std::vec::Vec;
+use {
+    super::{instruction_slice, read_u16},
+    pinocchio::pubkey::{pubkey_eq, Pubkey},
+    pinocchio_pubkey::pubkey,
+    typhoon_accounts::{Instructions, IntrospectedInstruction},
+    typhoon_errors::{Error, ErrorCode},
+};
+
+pub const SECP256K1_PROGRAM_ID: Pubkey = pubkey!("KeccakSecp256k11111111111111111111111111111");
+
+pub const SECP256K1_SIGNATURE_SIZE: usize = 64;
+pub const SECP256K1_ETH_ADDRESS_SIZE: usize = 20;
+pub const SECP256K1_SIGNATURE_OFFSETS_SIZE: usize = 11;
+pub const SECP256K1_SIGNATURE_OFFSETS_START: usize = 1;
+
+/// Offsets of a signature in a Secp256k1 program instruction, instruction indexes are
+/// absolute indexes in the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u8,
+    pub eth_address_offset: u16,
+    pub eth_address_instruction_index: u8,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u8,
+}
+
+impl Secp256k1SignatureOffsets {
+    #[inline(always)]
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < SECP256K1_SIGNATURE_OFFSETS_SIZE {
+            return None;
+        }
+
+        Some(Secp256k1SignatureOffsets {
+            signature_offset: read_u16(data, 0),
+            signature_instruction_index: data[2],
+            eth_address_offset: read_u16(data, 3),
+            eth_address_instruction_index: data[5],
+            message_data_offset: read_u16(data, 6),
+            message_data_size: read_u16(data, 8),
+            message_instruction_index: data[10],
+        })
+    }
+
+    #[inline(always)]
+    pub fn to_bytes(&self) -> [u8; SECP256K1_SIGNATURE_OFFSETS_SIZE] {
+        let mut bytes = [0; SECP256K1_SIGNATURE_OFFSETS_SIZE];
+        bytes[0..2].copy_from_slice(&self.signature_offset.to_le_bytes());
+        bytes[2] = self.signature_instruction_index;
+        bytes[3..5].copy_from_slice(&self.eth_address_offset.to_le_bytes());
+        bytes[5] = self.eth_address_instruction_index;
+        bytes[6..8].copy_from_slice(&self.message_data_offset.to_le_bytes());
+        bytes[8..10].copy_from_slice(&self.message_data_size.to_le_bytes());
+        bytes[10] = self.message_instruction_index;
+
+        bytes
+    }
+}
+
+/// A signature verified by a Secp256k1 program instruction.
+pub struct Secp256k1Signature<'a> {
+    pub eth_address: &'a [u8; SECP256K1_ETH_ADDRESS_SIZE],
+    pub signature: &'a [u8; SECP256K1_SIGNATURE_SIZE],
+    pub recovery_id: u8,
+    pub message: &'a [u8],
+}
+
+/// Iterates over the signatures verified by a Secp256k1 program instruction.
+pub fn secp256k1_signatures<'a>(
+    instructions: &'a Instructions,
+    instruction: IntrospectedInstruction<'a>,
+) -> impl Iterator<Item = Result<Secp256k1Signature<'a>, Error>> + 'a {
+    let data = instruction.data();
+    let count = data.first().copied().unwrap_or_default() as usize;
+
+    (0..count).map(move |i| {
+        let start = SECP256K1_SIGNATURE_OFFSETS_START + i * SECP256K1_SIGNATURE_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..)
+            .and_then(Secp256k1SignatureOffsets::from_bytes)
+            .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+
+        let signature = instruction_slice(
+            instructions,
+            data,
+            Some(offsets.signature_instruction_index as usize),
+            offsets.signature_offset,
+            SECP256K1_SIGNATURE_SIZE + 1,
+        )?;
+        let eth_address = instruction_slice(
+            instructions,
+            data,
+            Some(offsets.eth_address_instruction_index as usize),
+            offsets.eth_address_offset,
+            SECP256K1_ETH_ADDRESS_SIZE,
+        )?;
+        let message = instruction_slice(
+            instructions,
+            data,
+            Some(offsets.message_instruction_index as usize),
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        Ok(Secp256k1Signature {
+            // SAFETY: The slices have at least the size of the arrays.
+            eth_address: unsafe {
+                &*(eth_address.as_ptr() as *const [u8; SECP256K1_ETH_ADDRESS_SIZE])
+            },
+            signature: unsafe { &*(signature.as_ptr() as *const [u8; SECP256K1_SIGNATURE_SIZE]) },
+            recovery_id: signature[SECP256K1_SIGNATURE_SIZE],
+            message,
+        })
+    })
+}
+
+/// Checks that a Secp256k1 program instruction of the transaction verified `signature` of
+/// `message` by `eth_address`.
+pub fn require_secp256k1_signature(
+    instructions: &Instructions,
+    eth_address: &[u8; SECP256K1_ETH_ADDRESS_SIZE],
+    message: &[u8],
+    signature: &[u8; SECP256K1_SIGNATURE_SIZE],
+    recovery_id: u8,
+) -> Result<(), Error> {
+    for instruction in instructions.iter() {
+        let instruction = instruction?;
+        if !pubkey_eq(instruction.program_id(), &SECP256K1_PROGRAM_ID) {
+            continue;
+        }
+
+        for verified in secp256k1_signatures(instructions, instruction) {
+            let verified = verified?;
+            if verified.eth_address == eth_address
+                && verified.signature == signature
+                && verified.recovery_id == recovery_id
+                && verified.message == message
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(ErrorCode::SignatureNotVerified.into())
+}
+
+/// Builds the data of a Secp256k1 program instruction verifying one signature, `instruction_index`
+/// is the index of this instruction in the transaction.
+#[cfg(any(test, feature = "std"))]
+pub fn new_secp256k1_instruction_data(
+    eth_address: &[u8; SECP256K1_ETH_ADDRESS_SIZE],
+    message: &[u8],
+    signature: &[u8; SECP256K1_SIGNATURE_SIZE],
+    recovery_id: u8,
+    instruction_index: u8,
+) -> Vec<u8> {
+    let eth_address_offset = SECP256K1_SIGNATURE_OFFSETS_START + SECP256K1_SIGNATURE_OFFSETS_SIZE;
+    let signature_offset = eth_address_offset + SECP256K1_ETH_ADDRESS_SIZE;
+    let message_data_offset = signature_offset + SECP256K1_SIGNATURE_SIZE + 1;
+
+    let offsets = Secp256k1SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: instruction_index,
+        eth_address_offset: eth_address_offset as u16,
+        eth_address_instruction_index: instruction_index,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: instruction_index,
+    };
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.push(1);
+    data.extend_from_slice(&offsets.to_bytes());
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(signature);
+    data.push(recovery_id);
+    data.extend_from_slice(message);
+
+    data
+}
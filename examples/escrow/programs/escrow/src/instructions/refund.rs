@@ -1,15 +1,15 @@
 use {
     escrow_interface::state::Escrow,
-    typhoon::{instruction::CpiSigner, lib::CloseAccount, prelude::*},
-    typhoon_token::{
-        spl_instructions::{CloseAccount as SplCloseAccount, Transfer},
-        TokenAccount, TokenProgram,
-    },
+    typhoon::{instruction::CpiSigner, prelude::*},
+    typhoon_token::{TokenAccount, TokenAccountCpi, TokenProgram},
 };
 
 #[context]
 pub struct Refund {
     pub maker: Mut<Signer>,
+    #[constraint(
+        close = maker
+    )]
     pub escrow: Mut<Account<Escrow>>,
     pub mint_a: UncheckedAccount,
     #[constraint(
@@ -30,22 +30,13 @@ pub fn refund(ctx: Refund) -> ProgramResult {
 
     let amount = { ctx.vault.data()?.amount() };
 
-    Transfer {
-        from: ctx.vault.as_ref(),
-        to: ctx.maker_ata_a.as_ref(),
-        authority: ctx.escrow.as_ref(),
+    ctx.vault.transfer(
+        &ctx.maker_ata_a,
+        &ctx.escrow,
         amount,
-    }
-    .invoke_signed(&[signer.clone()])?;
-
-    SplCloseAccount {
-        account: ctx.vault.as_ref(),
-        authority: ctx.escrow.as_ref(),
-        destination: ctx.maker.as_ref(),
-    }
-    .invoke_signed(&[signer])?;
-
-    ctx.escrow.close(&ctx.maker)?;
+        Some(&[signer.clone()]),
+    )?;
+    ctx.vault.close_account(&ctx.maker, &ctx.escrow, Some(&[signer]))?;
 
     Ok(())
 }
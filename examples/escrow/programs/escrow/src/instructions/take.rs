@@ -1,9 +1,8 @@
 use {
     escrow_interface::state::Escrow,
-    typhoon::{instruction::CpiSigner, lib::CloseAccount, prelude::*},
+    typhoon::{instruction::CpiSigner, prelude::*},
     typhoon_token::{
-        spl_instructions::{CloseAccount as SplCloseAccount, Transfer},
-        AtaTokenProgram, Mint, SplCreateToken, TokenAccount, TokenProgram,
+        AtaTokenProgram, Mint, SplCreateToken, TokenAccount, TokenAccountCpi, TokenProgram,
     },
 };
 
@@ -11,6 +10,9 @@ use {
 pub struct Take {
     pub taker: Mut<Signer>,
     pub maker: Mut<SystemAccount>,
+    #[constraint(
+        close = maker
+    )]
     pub escrow: Mut<Account<Escrow>>,
     pub mint_a: InterfaceAccount<Mint>,
     pub mint_b: InterfaceAccount<Mint>,
@@ -50,31 +52,17 @@ pub fn take(ctx: Take) -> ProgramResult {
     let seeds = seeds!(b"escrow", ctx.maker.key(), seed.as_ref(), &bump);
     let signer = CpiSigner::from(&seeds);
 
-    Transfer {
-        from: ctx.vault.as_ref(),
-        to: ctx.taker_ata_a.as_ref(),
-        authority: ctx.escrow.as_ref(),
+    ctx.vault.transfer_checked(
+        &ctx.mint_a,
+        &ctx.taker_ata_a,
+        &ctx.escrow,
         amount,
-    }
-    .invoke_signed(&[signer.clone()])?;
-
-    SplCloseAccount {
-        account: ctx.vault.as_ref(),
-        authority: ctx.escrow.as_ref(),
-        destination: ctx.maker.as_ref(),
-    }
-    .invoke_signed(&[signer])?;
-
-    drop(escrow);
-    ctx.escrow.close(&ctx.maker)?;
+        Some(&[signer.clone()]),
+    )?;
+    ctx.vault.close_account(&ctx.maker, &ctx.escrow, Some(&[signer]))?;
 
-    Transfer {
-        from: ctx.taker_ata_b.as_ref(),
-        to: ctx.maker_ata_b.as_ref(),
-        authority: ctx.taker.as_ref(),
-        amount: receive,
-    }
-    .invoke()?;
+    ctx.taker_ata_b
+        .transfer_checked(&ctx.mint_b, &ctx.maker_ata_b, &ctx.taker, receive, None)?;
 
     Ok(())
 }